@@ -1,263 +1,5281 @@
 pub mod z_sv {
 
+    use std::collections::{HashMap, HashSet};
     use std::ffi::CString;
+    use std::ops::{Range, RangeInclusive};
+    use std::time::Duration;
 
-    #[derive(Debug, Clone, PartialEq)]
+    /// An owned string with helpers for searching and splitting.
+    ///
+    /// Every index taken or returned by the `find_*`, `rfind_*`, and
+    /// `split_*` methods is a byte offset into `self.data`, the same as the
+    /// indices used by `str`. An index returned by a search always lies on a
+    /// character boundary, so it can be passed straight to `split_index` or
+    /// used to slice `self.data`.
+    ///
+    /// Byte offsets are measured against `len`. The few methods that count
+    /// in characters instead, such as `split_middle`, `split_columns`, and
+    /// `split_all_char_in_range`, say so in their docs and are measured
+    /// against `char_count`.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
     pub struct ZSV {
         pub data: String,
     }
 
     impl ZSV {
         /// Finds the first instance of `f` in `self.data`, and returns either
-        /// `Some(index)`, or `None` if the character doesn't exist within
-        /// `self.data`.
+        /// `Some(index)` with the byte offset of the character, or `None` if
+        /// the character doesn't exist within `self.data`.
         pub fn find_char(&self, f: char) -> Option<usize> {
-            let mut indx: usize = 0usize;
-            for c in self.data.chars() {
-                if c == f {
-                    return Some(indx);
+            self.as_view().find_char(f)
+        }
+
+        /// Finds the first instance of `f` in `self.data`, and returns either
+        /// `Some(index)` with the byte offset of the match, or `None` if the
+        /// string isn't contained within `self.data`. if `f.data.len()` is 0,
+        /// we will always return `Some(0)`
+        pub fn find_string(&self, f: &ZSV) -> Option<usize> {
+            self.as_view().find_string(&f.as_view())
+        }
+
+        /// Finds the last instance of `f` in `self.data`, and returns either
+        /// `Some(index)` with the byte offset of the character, or `None` if
+        /// the character doesn't exist within `self.data`. This matches
+        /// `str::rfind`.
+        pub fn rfind_char(&self, f: char) -> Option<usize> {
+            self.as_view().rfind_char(f)
+        }
+
+        /// Finds the last instance of `f` in `self.data`, and returns either
+        /// `Some(index)` with the byte offset of the match, or `None` if the
+        /// string isn't contained within `self.data`. if `f.data.len()` is 0,
+        /// we will always return the byte offset of the last character. if
+        /// `self.data.len()` is 0, we will return None.
+        pub fn rfind_string(&self, f: &ZSV) -> Option<usize> {
+            self.as_view().rfind_string(&f.as_view())
+        }
+
+        /// Splits a ZSV into a tuple of `(a: Option<ZSV>, b: Option<ZSV>)`
+        /// where `Some(a)` is all data left of the first instance of the
+        /// seperator character `s`, and `Some(b)` is all data to the right
+        /// of and including the seperator character `s`. if `s` isn't found,
+        /// we will return `(Some(self), None)`. Note that the seperator stays
+        /// on the right half, so `"key=value"` splits into `"key"` and
+        /// `"=value"`; use `split_once_char` to drop it.
+        pub fn split_char(&self, s: char) -> (Option<ZSV>, Option<ZSV>) {
+            let (left, right) = self.as_view().split_char(s);
+            (left.map(ZSV::from), right.map(ZSV::from))
+        }
+
+        /// Splits a ZSV into a tuple of `(a: Option<ZSV>, b: Option<ZSV>)`
+        /// where `Some(a)` is all data left of the byte offset `indx`, and
+        /// `Some(b)` is all data to the right of and including `indx`. if
+        /// `indx` is past the end of the data, we will return `(None, None)`.
+        ///
+        /// # Panics
+        /// Panics if `indx` is not on a character boundary.
+        pub fn split_index(&self, indx: usize) -> (Option<ZSV>, Option<ZSV>) {
+            let (left, right) = self.as_view().split_index(indx);
+            (left.map(ZSV::from), right.map(ZSV::from))
+        }
+
+        /// Splits a ZSV into a tuple of `(a: Option<ZSV>, b: Option<ZSV>)`
+        /// This function is defined as `self.split_index(self.find_string(s))`
+        pub fn split_string(&self, s: &ZSV) -> (Option<ZSV>, Option<ZSV>) {
+            let (left, right) = self.as_view().split_string(&s.as_view());
+            (left.map(ZSV::from), right.map(ZSV::from))
+        }
+
+        /// Returns a borrowed `ZSVRef` view of the data, whose searching and
+        /// splitting methods hand back sub-views instead of allocating.
+        pub fn as_view(&self) -> ZSVRef<'_> {
+            ZSVRef { data: &self.data }
+        }
+
+        /// Returns an owned copy of the `String`
+        pub fn as_owned_string(&self) -> String {
+            self.data.to_owned()
+        }
+
+        /// Splits a ZSV on the last `n - 1` instances of the seperator
+        /// character `c`, producing at most `n` pieces. The pieces are
+        /// returned in left-to-right order, so the first piece holds all of
+        /// the remaining leading data (including any seperators that weren't
+        /// split on), and the last piece is everything after the final
+        /// seperator. if `n` is 0, we will return an empty `Vec`.
+        pub fn rsplitn_char(&self, n: usize, c: char) -> Vec<ZSV> {
+            let mut pieces: Vec<ZSV> = self.data.rsplitn(n, c).map(ZSV::from).collect();
+            pieces.reverse();
+            pieces
+        }
+
+        /// Returns `true` if `self` and `other` contain exactly the same
+        /// characters, the same number of times each, in any order. Strings
+        /// of differing lengths are never anagrams of one another.
+        pub fn is_anagram(&self, other: &ZSV) -> bool {
+            if self.data.len() != other.data.len() {
+                return false;
+            }
+            let mut a: Vec<char> = self.to_chars();
+            let mut b: Vec<char> = other.to_chars();
+            a.sort_unstable();
+            b.sort_unstable();
+            a == b
+        }
+
+        /// Like `is_anagram`, but whitespace is dropped and both sides are
+        /// lowercased before comparing, so `"Dormitory"` and `"dirty room"`
+        /// are considered anagrams.
+        pub fn is_anagram_ignore_whitespace_and_case(&self, other: &ZSV) -> bool {
+            let mut a: Vec<char> = self
+                .data
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .flat_map(char::to_lowercase)
+                .collect();
+            let mut b: Vec<char> = other
+                .data
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .flat_map(char::to_lowercase)
+                .collect();
+            if a.len() != b.len() {
+                return false;
+            }
+            a.sort_unstable();
+            b.sort_unstable();
+            a == b
+        }
+
+        /// Splits a ZSV into two halves at its middle character. When the
+        /// number of characters is odd, the left half gets the extra one.
+        /// This counts characters rather than bytes, so it will never cut a
+        /// multibyte character in two.
+        pub fn split_middle(&self) -> (ZSV, ZSV) {
+            let left_chars: usize = (self.data.chars().count() + 1) / 2;
+            let seperator: usize = self
+                .data
+                .char_indices()
+                .nth(left_chars)
+                .map_or(self.data.len(), |(i, _)| i);
+            (
+                ZSV::from(&self.data[..seperator]),
+                ZSV::from(&self.data[seperator..]),
+            )
+        }
+
+        /// Formats `value` with exactly `decimals` digits after the decimal
+        /// point, padding with trailing zeros as needed. Scientific notation
+        /// is never used, so very large or very small values are written out
+        /// in full. Values are rounded to the nearest representable result,
+        /// with exact ties rounding to even (e.g. `0.125` at 2 decimals is
+        /// `"0.12"`). A negative value that rounds to zero, such as `-0.001`
+        /// at 2 decimals, is written without its sign as `"0.00"`. `NaN` and
+        /// the infinities are written as `"NaN"`, `"inf"`, and `"-inf"`.
+        pub fn from_f64_fixed(value: f64, decimals: usize) -> ZSV {
+            let formatted: String = format!("{:.*}", decimals, value);
+            match formatted.strip_prefix('-') {
+                Some(unsigned) if unsigned.bytes().all(|b| b == b'0' || b == b'.') => {
+                    ZSV::from(unsigned)
+                }
+                _ => ZSV::from(formatted),
+            }
+        }
+
+        /// Returns the number of leading characters that `self` and `other`
+        /// have in common.
+        pub fn common_prefix_len(&self, other: &ZSV) -> usize {
+            self.data
+                .chars()
+                .zip(other.data.chars())
+                .take_while(|(a, b)| a == b)
+                .count()
+        }
+
+        /// Returns the character index of the first position at which `self`
+        /// and `other` differ, which is where two similar strings need to be
+        /// lined up against each other. This is the same value as
+        /// `common_prefix_len`.
+        pub fn align_offset(&self, other: &ZSV) -> usize {
+            self.common_prefix_len(other)
+        }
+
+        /// Returns the parts of `self` and `other` that remain after their
+        /// common prefix is removed, in that order.
+        pub fn aligned_remainders(&self, other: &ZSV) -> (ZSV, ZSV) {
+            let offset: usize = self.align_offset(other);
+            let tail = |data: &str| -> ZSV {
+                let start: usize = data
+                    .char_indices()
+                    .nth(offset)
+                    .map_or(data.len(), |(i, _)| i);
+                ZSV::from(&data[start..])
+            };
+            (tail(&self.data), tail(&other.data))
+        }
+
+        /// Returns every run of `k` consecutive whitespace-separated words
+        /// (a word-level k-shingle), with the words of each run joined by a
+        /// single space. The shingles overlap, so `n` words produce
+        /// `n - k + 1` shingles. if `k` is 0 or greater than the number of
+        /// words, we will return an empty `Vec`.
+        pub fn shingles(&self, k: usize) -> Vec<ZSV> {
+            let words: Vec<&str> = self.data.split_whitespace().collect();
+            if k == 0 || k > words.len() {
+                return Vec::new();
+            }
+            words.windows(k).map(|w| ZSV::from(w.join(" "))).collect()
+        }
+
+        /// Removes trailing spaces and tabs from the end of every line. Line
+        /// endings (`\n` or `\r\n`) are kept as they were, including whether
+        /// or not the final line ends with a newline.
+        pub fn trim_trailing_whitespace_lines(&self) -> ZSV {
+            let mut out: String = String::with_capacity(self.data.len());
+            for line in self.data.split_inclusive('\n') {
+                let (body, ending) = if let Some(body) = line.strip_suffix("\r\n") {
+                    (body, "\r\n")
+                } else if let Some(body) = line.strip_suffix('\n') {
+                    (body, "\n")
+                } else {
+                    (line, "")
+                };
+                out.push_str(body.trim_end_matches([' ', '\t']));
+                out.push_str(ending);
+            }
+            ZSV::from(out)
+        }
+
+        /// Collapses every run of more than `max` consecutive blank lines
+        /// down to `max` blank lines. A line counts as blank if it is empty
+        /// or contains only whitespace. With `max = 0`, all blank lines are
+        /// removed.
+        pub fn collapse_blank_lines(&self, max: usize) -> ZSV {
+            let mut out: String = String::with_capacity(self.data.len());
+            let mut run: usize = 0;
+            for line in self.data.split_inclusive('\n') {
+                if line.trim().is_empty() {
+                    run += 1;
+                    if run > max {
+                        continue;
+                    }
                 } else {
-                    indx += 1;
+                    run = 0;
                 }
+                out.push_str(line);
             }
-            None
+            ZSV::from(out)
         }
 
-        /// Finds the first instance of `f` in `self.data`, and returns either
-        /// `Some(index)`, or `None` if the string isn't contained within
-        /// `self.data`. if `f.data.len()` is 0, we will always return
-        /// `Some(0)`
-        pub fn find_string(&self, f: &ZSV) -> Option<usize> {
-            if f.data.len() > self.data.len() {
-                return None;
+        /// Inspects the leading whitespace of every indented line and reports
+        /// whether the text is indented with tabs or with spaces, and if
+        /// spaces, how many make up one level (the greatest common divisor of
+        /// the leading space counts). Blank lines are ignored. For mixed
+        /// indentation, whichever style starts more lines wins, with ties
+        /// going to `IndentStyle::Tabs`. if no line is indented, we will
+        /// return `None`.
+        pub fn detect_indent(&self) -> Option<IndentStyle> {
+            let mut tab_lines: usize = 0;
+            let mut space_lines: usize = 0;
+            let mut unit: usize = 0;
+            for line in self.data.lines() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                if line.starts_with('\t') {
+                    tab_lines += 1;
+                } else if line.starts_with(' ') {
+                    space_lines += 1;
+                    let mut a: usize = line.len() - line.trim_start_matches(' ').len();
+                    let mut b: usize = unit;
+                    while b != 0 {
+                        (a, b) = (b, a % b);
+                    }
+                    unit = a;
+                }
             }
-            if f.data.len() == 0 {
-                return Some(0usize);
+            if tab_lines == 0 && space_lines == 0 {
+                None
+            } else if tab_lines >= space_lines {
+                Some(IndentStyle::Tabs)
+            } else {
+                Some(IndentStyle::Spaces(unit))
             }
-            let data_char_array_cache: Vec<char> = self.data.chars().collect();
-            let f_char_array_cache: Vec<char> = f.data.chars().collect();
-            'outter: for a in 0..self.data.len() {
-                for b in 0..f.data.len() {
-                    if data_char_array_cache[a + b] != f_char_array_cache[b] {
-                        continue 'outter;
+        }
+
+        /// Returns the contents of every top-level group delimited by `open`
+        /// and `close`, without the outer delimiters themselves. Nested groups
+        /// are left inside their parent's contents rather than being returned
+        /// as separate entries, so `"(a(b))(c)"` yields `"a(b)"` and `"c"`.
+        /// Unbalanced input is tolerated: a `close` with no matching `open` is
+        /// ignored, and a group that is still open at the end of the data is
+        /// not returned.
+        pub fn top_level_groups(&self, open: char, close: char) -> Vec<ZSV> {
+            let mut groups: Vec<ZSV> = Vec::new();
+            let mut depth: usize = 0;
+            let mut start: usize = 0;
+            for (i, c) in self.data.char_indices() {
+                if c == open {
+                    if depth == 0 {
+                        start = i + c.len_utf8();
+                    }
+                    depth += 1;
+                } else if c == close && depth > 0 {
+                    depth -= 1;
+                    if depth == 0 {
+                        groups.push(ZSV::from(&self.data[start..i]));
                     }
                 }
-                return Some(a);
             }
-            None
+            groups
         }
 
-        /// Finds the last instance of `f` in `self.data`, and returns either
-        /// `Some(index)`, or `None` if the character doesn't exist within
-        /// `self.data`.
-        pub fn rfind_char(&self, f: char) -> Option<usize> {
-            let mut indx: usize = self.data.len();
-            for c in self.data.chars().rev() {
-                if c == f {
-                    return Some(indx);
+        /// Splits a ZSV on every instance of `sep` that isn't preceded by the
+        /// `escape` character, removing the seperators. Within each field,
+        /// `escape` followed by `sep` becomes a literal `sep`, and `escape`
+        /// followed by another `escape` becomes a single `escape`. An `escape`
+        /// followed by anything else, or a lone `escape` at the very end of
+        /// the data, is kept as-is. Like `str::split`, empty input yields a
+        /// single empty field.
+        pub fn split_all_char_escaped(&self, sep: char, escape: char) -> Vec<ZSV> {
+            let mut fields: Vec<ZSV> = Vec::new();
+            let mut field: String = String::new();
+            let mut chars = self.data.chars();
+            while let Some(c) = chars.next() {
+                if c == escape {
+                    match chars.next() {
+                        Some(n) if n == sep || n == escape => field.push(n),
+                        Some(n) => {
+                            field.push(c);
+                            field.push(n);
+                        }
+                        None => field.push(c),
+                    }
+                } else if c == sep {
+                    fields.push(ZSV::from(std::mem::take(&mut field)));
                 } else {
-                    indx -= 1;
+                    field.push(c);
                 }
             }
-            None
+            fields.push(ZSV::from(field));
+            fields
         }
 
-        /// Finds the last instance of `f` in `self.data`, and returns either
-        /// `Some(index)`, or `None` if the string isn't contained within
-        /// `self.data`. if `f.data.len()` is 0, we will always return
-        /// `Some(self.data.len()-1)`. if `self.data.len()` is 0, we will
-        /// return None.
-        pub fn rfind_string(&self, f: &ZSV) -> Option<usize> {
-            if f.data.len() > self.data.len() {
-                return None;
+        /// Returns every contiguous run of `n` bytes in the underlying UTF-8
+        /// buffer, in order. This works purely on bytes and pays no attention
+        /// to character boundaries. if `n` is 0 or larger than the data, we
+        /// will return an empty `Vec`.
+        pub fn byte_windows(&self, n: usize) -> Vec<&[u8]> {
+            if n == 0 {
+                return Vec::new();
             }
-            if self.data.len() == 0 {
-                return None;
+            self.data.as_bytes().windows(n).collect()
+        }
+
+        /// Parses a duration written as a whole number followed immediately
+        /// by a unit suffix: `ns`, `us`, `ms`, `s`, `m`, or `h` (e.g. `"500ms"`,
+        /// `"2s"`, `"5m"`, `"1h"`). Whitespace around the value is trimmed. A
+        /// missing or unknown suffix is a `ParseError::InvalidUnit`.
+        pub fn parse_duration(&self) -> Result<Duration, ParseError> {
+            let trimmed: &str = self.data.trim();
+            if trimmed.is_empty() {
+                return Err(ParseError::Empty);
             }
-            if f.data.len() == 0 {
-                return Some(self.data.len() - 1);
+            let split: usize = trimmed
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(trimmed.len());
+            let (number, unit) = trimmed.split_at(split);
+            let value: u64 = number
+                .parse()
+                .map_err(|_| ParseError::InvalidNumber(ZSV::from(number)))?;
+            let seconds = |scale: u64| -> Result<Duration, ParseError> {
+                value
+                    .checked_mul(scale)
+                    .map(Duration::from_secs)
+                    .ok_or_else(|| ParseError::InvalidNumber(ZSV::from(number)))
+            };
+            match unit {
+                "ns" => Ok(Duration::from_nanos(value)),
+                "us" => Ok(Duration::from_micros(value)),
+                "ms" => Ok(Duration::from_millis(value)),
+                "s" => seconds(1),
+                "m" => seconds(60),
+                "h" => seconds(60 * 60),
+                _ => Err(ParseError::InvalidUnit(ZSV::from(unit))),
             }
-            let data_char_array_cache: Vec<char> = self.data.chars().collect();
-            let f_char_array_cache: Vec<char> = f.data.chars().collect();
-            'outter: for a in (0..=(self.data.len() - f.data.len())).rev() {
-                for b in 0..f.data.len() {
-                    if data_char_array_cache[a + b] != f_char_array_cache[b] {
-                        continue 'outter;
+        }
+
+        /// Computes the classic four-character American Soundex code of the
+        /// ASCII letters in `self.data`, such as `"R163"` for both `"Robert"`
+        /// and `"Rupert"`. Every non-letter character is ignored. if there are
+        /// no letters at all, we will return an empty ZSV.
+        pub fn soundex(&self) -> ZSV {
+            fn code(c: char) -> Option<char> {
+                match c {
+                    'B' | 'F' | 'P' | 'V' => Some('1'),
+                    'C' | 'G' | 'J' | 'K' | 'Q' | 'S' | 'X' | 'Z' => Some('2'),
+                    'D' | 'T' => Some('3'),
+                    'L' => Some('4'),
+                    'M' | 'N' => Some('5'),
+                    'R' => Some('6'),
+                    _ => None,
+                }
+            }
+            let mut letters = self
+                .data
+                .chars()
+                .filter(char::is_ascii_alphabetic)
+                .map(|c| c.to_ascii_uppercase());
+            let first: char = match letters.next() {
+                Some(c) => c,
+                None => return ZSV::from(""),
+            };
+            let mut out: String = String::from(first);
+            let mut last: Option<char> = code(first);
+            for c in letters {
+                if out.len() == 4 {
+                    break;
+                }
+                match code(c) {
+                    Some(d) if last != Some(d) => {
+                        out.push(d);
+                        last = Some(d);
                     }
+                    Some(_) => {}
+                    // 'H' and 'W' don't separate letters with the same code,
+                    // but vowels do.
+                    None if c == 'H' || c == 'W' => {}
+                    None => last = None,
                 }
-                return Some(a);
             }
-            None
+            while out.len() < 4 {
+                out.push('0');
+            }
+            ZSV::from(out)
         }
 
-        /// Splits a ZSV into a tuple of `(a: Option<ZSV>, b: Option<ZSV>)`
-        /// where `Some(a)` is all data left of the first instance of the
-        /// seperator character `s`, and `Some(b)` is all data to the right
-        /// of and including the seperator character `s`.
-        pub fn split_char(&self, s: char) -> (Option<ZSV>, Option<ZSV>) {
-            if let Some(seperator) = self.find_char(s) {
-                if seperator >= self.data.len() {
-                    return (None, None);
+        /// if the data starts with `"#!"`, returns the first line (without its
+        /// line ending) along with everything after that line. Otherwise, we
+        /// will return `(None, self.clone())`.
+        pub fn strip_shebang(&self) -> (Option<ZSV>, ZSV) {
+            if !self.data.starts_with("#!") {
+                return (None, self.clone());
+            }
+            let (line, rest) = match self.data.find('\n') {
+                Some(i) => (&self.data[..i], &self.data[i + 1..]),
+                None => (&self.data[..], ""),
+            };
+            let line: &str = line.strip_suffix('\r').unwrap_or(line);
+            (Some(ZSV::from(line)), ZSV::from(rest))
+        }
+
+        /// Records the byte offset at which every line starts, returning a
+        /// `LineMap` that can translate between byte offsets and
+        /// `(line, column)` positions.
+        pub fn build_line_map(&self) -> LineMap {
+            let mut line_starts: Vec<usize> = vec![0];
+            line_starts.extend(self.data.match_indices('\n').map(|(i, _)| i + 1));
+            LineMap {
+                line_starts,
+                len: self.data.len(),
+            }
+        }
+
+        /// Slices a single fixed-width row into columns, where `col_starts`
+        /// holds the character position at which each column begins, in
+        /// ascending order. Each column runs up to the start of the next one,
+        /// and the last column runs to the end of the line. A trailing line
+        /// ending is not included, and the padding inside each column is left
+        /// untouched. Columns that start past the end of the row come back
+        /// empty.
+        pub fn split_columns(&self, col_starts: &[usize]) -> Vec<ZSV> {
+            let line: &str = self.data.trim_end_matches(['\r', '\n']);
+            let byte_at = |pos: usize| -> usize {
+                line.char_indices().nth(pos).map_or(line.len(), |(i, _)| i)
+            };
+            let mut columns: Vec<ZSV> = Vec::with_capacity(col_starts.len());
+            for (i, &start) in col_starts.iter().enumerate() {
+                let from: usize = byte_at(start);
+                let to: usize = match col_starts.get(i + 1) {
+                    Some(&next) => byte_at(next).max(from),
+                    None => line.len(),
+                };
+                columns.push(ZSV::from(&line[from..to]));
+            }
+            columns
+        }
+
+        /// Computes the Sørensen–Dice coefficient between the character
+        /// bigrams of `self` and `other`: twice the number of shared bigrams
+        /// (counted with multiplicity) divided by the total number of bigrams
+        /// in both. The result ranges from `0.0` (nothing in common) to `1.0`
+        /// (identical bigrams). Strings shorter than two characters have no
+        /// bigrams, so if either side is that short, we will return `1.0` when
+        /// the two strings are equal and `0.0` otherwise.
+        pub fn dice_coefficient(&self, other: &ZSV) -> f64 {
+            let a: Vec<char> = self.to_chars();
+            let b: Vec<char> = other.to_chars();
+            if a.len() < 2 || b.len() < 2 {
+                return if a == b { 1.0 } else { 0.0 };
+            }
+            let mut counts: HashMap<(char, char), usize> = HashMap::new();
+            for w in a.windows(2) {
+                *counts.entry((w[0], w[1])).or_insert(0) += 1;
+            }
+            let mut shared: usize = 0;
+            for w in b.windows(2) {
+                if let Some(n) = counts.get_mut(&(w[0], w[1])) {
+                    if *n > 0 {
+                        *n -= 1;
+                        shared += 1;
+                    }
                 }
-                let left: ZSV = ZSV::from(&self.data[..seperator]);
-                let right: ZSV = ZSV::from(&self.data[seperator..]);
-                return (Some(left), Some(right));
-            } else {
-                return (Some(self.clone()), None);
             }
+            (2 * shared) as f64 / ((a.len() - 1) + (b.len() - 1)) as f64
         }
 
-        /// Splits a ZSV into a tuple of `(a: Option<ZSV>, b: Option<ZSV>)`
-        /// where `Some(a)` is all data left of the index: `indx`, and
-        /// `Some(b)` is all data to the right of and including `indx`
-        pub fn split_index(&self, indx: usize) -> (Option<ZSV>, Option<ZSV>) {
-            if indx >= self.data.len() {
-                return (None, None);
+        /// Returns the longest run of characters that appears contiguously in
+        /// both `self` and `other`. When several runs share the longest
+        /// length, the one that ends earliest in `self` is returned. if the
+        /// two have no characters in common, we will return an empty ZSV.
+        pub fn longest_common_substring(&self, other: &ZSV) -> ZSV {
+            let a: Vec<char> = self.to_chars();
+            let b: Vec<char> = other.to_chars();
+            // prev[j] / curr[j] hold the length of the common run ending at
+            // a[i - 1] and b[j - 1].
+            let mut prev: Vec<usize> = vec![0; b.len() + 1];
+            let mut curr: Vec<usize> = vec![0; b.len() + 1];
+            let mut best_len: usize = 0;
+            let mut best_end: usize = 0;
+            for i in 1..=a.len() {
+                for j in 1..=b.len() {
+                    curr[j] = if a[i - 1] == b[j - 1] {
+                        prev[j - 1] + 1
+                    } else {
+                        0
+                    };
+                    if curr[j] > best_len {
+                        best_len = curr[j];
+                        best_end = i;
+                    }
+                }
+                std::mem::swap(&mut prev, &mut curr);
             }
-            let left: ZSV = ZSV::from(&self.data[..indx]);
-            let right: ZSV = ZSV::from(&self.data[indx..]);
-            (Some(left), Some(right))
+            ZSV::from_chars(&a[best_end - best_len..best_end])
         }
 
-        /// Splits a ZSV into a tuple of `(a: Option<ZSV>, b: Option<ZSV>)`
-        /// This function is defined as `self.split_index(self.find_string(s))`
-        pub fn split_string(&self, s: &ZSV) -> (Option<ZSV>, Option<ZSV>) {
-            if let Some(indx) = self.find_string(s) {
-                return self.split_index(indx);
-            } else {
-                return (Some(self.clone()), None);
+        /// Wraps every non-overlapping instance of `needle` in `open` and
+        /// `close` (for example, ANSI colour codes), leaving the rest of the
+        /// data untouched. Matches are found left to right. if `needle` is
+        /// empty, we will return an unchanged copy.
+        pub fn highlight(&self, needle: &ZSV, open: &ZSV, close: &ZSV) -> ZSV {
+            if needle.data.is_empty() {
+                return self.clone();
+            }
+            let mut out: String = String::with_capacity(self.data.len());
+            let mut last: usize = 0;
+            for (i, m) in self.data.match_indices(needle.data.as_str()) {
+                out.push_str(&self.data[last..i]);
+                out.push_str(&open.data);
+                out.push_str(m);
+                out.push_str(&close.data);
+                last = i + m.len();
             }
+            out.push_str(&self.data[last..]);
+            ZSV::from(out)
         }
 
-        /// Returns an owned copy of the `String`
-        pub fn as_owned_string(&self) -> String {
-            return self.data.to_owned();
+        /// Splits the data into logical lines, where a physical line ending in
+        /// a backslash is continued onto the next one. The backslash and the
+        /// line ending are dropped when lines are joined. Line endings are
+        /// handled like `str::lines`, and a backslash on the very last line
+        /// is simply removed.
+        pub fn logical_lines(&self) -> Vec<ZSV> {
+            let mut lines: Vec<ZSV> = Vec::new();
+            let mut current: String = String::new();
+            let mut continued: bool = false;
+            for line in self.data.lines() {
+                if let Some(body) = line.strip_suffix('\\') {
+                    current.push_str(body);
+                    continued = true;
+                } else {
+                    current.push_str(line);
+                    lines.push(ZSV::from(std::mem::take(&mut current)));
+                    continued = false;
+                }
+            }
+            if continued {
+                lines.push(ZSV::from(current));
+            }
+            lines
         }
-    }
 
-    impl From<&str> for ZSV {
-        fn from(data: &str) -> ZSV {
-            ZSV {
-                data: data.to_owned(),
+        /// Returns a 64-bit FNV-1a hash of the UTF-8 bytes in `self.data`.
+        /// Unlike `std::hash::Hash`, the value is fixed by the algorithm and
+        /// will be the same across runs, platforms, and compiler versions, so
+        /// it is safe to persist.
+        pub fn stable_hash(&self) -> u64 {
+            let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+            for b in self.data.bytes() {
+                hash ^= b as u64;
+                hash = hash.wrapping_mul(0x0100_0000_01b3);
             }
+            hash
         }
-    }
-    impl From<String> for ZSV {
-        fn from(data: String) -> ZSV {
-            ZSV { data: data.clone() }
+
+        /// Maps the data onto one of `n` buckets, defined as
+        /// `self.stable_hash() % n`. The same data always lands in the same
+        /// bucket for a given `n`.
+        ///
+        /// # Panics
+        /// Panics if `n` is 0.
+        pub fn bucket(&self, n: usize) -> usize {
+            assert!(n != 0, "ZSV::bucket() called with zero buckets");
+            (self.stable_hash() % n as u64) as usize
         }
-    }
-    impl From<&CString> for ZSV {
-        fn from(data: &CString) -> ZSV {
-            ZSV {
-                data: data
-                    .to_str()
-                    .expect("Invalid UTF-8 provided to ZSV::From<&CString>()")
-                    .to_owned(),
+
+        /// Returns `true` if the letters in the data come from more than one
+        /// writing system, such as a Latin word with a Cyrillic `а` slipped
+        /// in, which is a common sign of a homograph attack. The scripts are
+        /// classified coarsely by code point range (Latin, Greek, Cyrillic,
+        /// Armenian, Hebrew, Arabic, and CJK, which covers Han, kana, and
+        /// Hangul together). Digits, punctuation, and whitespace don't belong
+        /// to any script and are ignored.
+        pub fn has_mixed_scripts(&self) -> bool {
+            #[derive(PartialEq)]
+            enum Script {
+                Latin,
+                Greek,
+                Cyrillic,
+                Armenian,
+                Hebrew,
+                Arabic,
+                Cjk,
+                Other,
+            }
+            fn script(c: char) -> Option<Script> {
+                if !c.is_alphabetic() {
+                    return None;
+                }
+                Some(match c as u32 {
+                    0x0000..=0x024F | 0x1E00..=0x1EFF => Script::Latin,
+                    0x0370..=0x03FF | 0x1F00..=0x1FFF => Script::Greek,
+                    0x0400..=0x052F => Script::Cyrillic,
+                    0x0530..=0x058F => Script::Armenian,
+                    0x0590..=0x05FF => Script::Hebrew,
+                    0x0600..=0x06FF => Script::Arabic,
+                    0x1100..=0x11FF
+                    | 0x3040..=0x30FF
+                    | 0x3400..=0x4DBF
+                    | 0x4E00..=0x9FFF
+                    | 0xAC00..=0xD7AF => Script::Cjk,
+                    _ => Script::Other,
+                })
+            }
+            let mut seen: Option<Script> = None;
+            for s in self.data.chars().filter_map(script) {
+                match &seen {
+                    Some(first) if *first != s => return true,
+                    Some(_) => {}
+                    None => seen = Some(s),
+                }
             }
+            false
         }
-    }
-}
-#[cfg(test)]
-mod tests {
 
-    use crate::z_sv::*;
-    use std::ffi::CString;
-    #[test]
-    fn test_from_cstr() -> Result<(), ()> {
-        let j: CString = CString::new("Hello, world!").unwrap();
-        let _f: ZSV = ZSV::from(&j);
-        Ok(())
-    }
+        /// Splits a path into its components on `'/'`. Empty segments, such
+        /// as the one before the leading `'/'` of an absolute path, after a
+        /// trailing `'/'`, or between doubled seperators, are dropped, so
+        /// `"/usr//bin/"` yields `"usr"` and `"bin"`.
+        pub fn path_components(&self) -> Vec<ZSV> {
+            self.data
+                .split('/')
+                .filter(|s| !s.is_empty())
+                .map(ZSV::from)
+                .collect()
+        }
 
-    #[test]
-    fn test_from_string() -> Result<(), ()> {
-        let j: String = "The Game.".to_owned();
-        let _f: ZSV = ZSV::from(j);
-        Ok(())
-    }
+        /// Like `path_components`, but `'\\'` is treated as a seperator too,
+        /// for Windows-style paths.
+        pub fn path_components_with_backslash(&self) -> Vec<ZSV> {
+            self.data
+                .split(['/', '\\'])
+                .filter(|s| !s.is_empty())
+                .map(ZSV::from)
+                .collect()
+        }
 
-    #[test]
-    fn test_find_string() -> Result<(), ()> {
-        let corpus: ZSV = ZSV::from(
-            "Lorem ipsum dolor sit amet, consectetur 
-                                    adipiscing elit. Duis vestibulum iaculis
-                                    orci ut laoreet. Nulla hendrerit sed nisl
-                                    nec tempor. Curabitur vitae tempus lorem,
-                                    quis mollis velit. Morbi pellentesque 
-                                    sodales turpis a vestibulum. Donec quis 
-                                    justo sed odio sodales bibendum in 
-                                    pellentesque erat. Pellentesque habitant 
-                                    morbi tristique senectus et netus et 
-                                    malesuada fames ac turpis egestas. 
-                                    Vestibulum tincidunt libero non vestibulum 
-                                    ultricies. Cras quis mi nunc. Donec vitae 
-                                    tortor cursus lacus aliquam aliquam nec 
-                                    gravida augue. Fusce iaculis est et est 
-                                    fermentum fringilla. Sed vestibulum arcu 
-                                    odio, at tristique nibh imperdiet vitae. 
-                                    Curabitur sed velit molestie leo mollis 
-                                    interdum sed id nunc. Sed sagittis 
-                                    scelerisque tincidunt.",
-        );
-        let text_to_find: ZSV = ZSV::from("et");
-        let loc = corpus.find_string(&text_to_find);
-        if let Some(loc) = loc {
-            if loc != 24 {
-                return Err(());
-            } else {
-                return Ok(());
+        /// Renders `rows` as a plain-text table, padding every cell with
+        /// spaces to the width (in characters) of the widest cell in its
+        /// column and joining the cells of a row with `" | "`. Each row is
+        /// written on its own line, ending in `'\n'`. The last cell of a row
+        /// isn't padded, so no line has trailing whitespace. Rows may have
+        /// differing numbers of cells; shorter rows simply end early.
+        pub fn render_table(rows: &[Vec<ZSV>]) -> ZSV {
+            let mut widths: Vec<usize> = Vec::new();
+            for row in rows {
+                for (i, cell) in row.iter().enumerate() {
+                    let width: usize = cell.data.chars().count();
+                    if i == widths.len() {
+                        widths.push(width);
+                    } else if width > widths[i] {
+                        widths[i] = width;
+                    }
+                }
             }
-        } else {
-            return Err(());
+            let mut out: String = String::new();
+            for row in rows {
+                for (i, cell) in row.iter().enumerate() {
+                    if i + 1 == row.len() {
+                        out.push_str(&cell.data);
+                    } else {
+                        out.push_str(&format!("{:<width$} | ", cell.data, width = widths[i]));
+                    }
+                }
+                out.push('\n');
+            }
+            ZSV::from(out)
         }
-    }
 
-    #[test]
-    fn test_rfind_string() -> Result<(), ()> {
-        let corpus: ZSV = ZSV::from(
-            "Lorem ipsum dolor sit amet, consectetur 
-                                    adipiscing elit. Duis vestibulum iaculis
-                                    orci ut laoreet. Nulla hendrerit sed nisl
-                                    nec tempor. Curabitur vitae tempus lorem,
-                                    quis mollis velit. Morbi pellentesque 
-                                    sodales turpis a vestibulum. Donec quis 
-                                    justo sed odio sodales bibendum in 
-                                    pellentesque erat. Pellentesque habitant 
-                                    morbi tristique senectus et netus et 
-                                    malesuada fames ac turpis egestas. 
-                                    Vestibulum tincidunt libero non vestibulum 
-                                    ultricies. Cras quis mi nunc. Donec vitae 
-                                    tortor cursus lacus aliquam aliquam nec 
-                                    gravida augue. Fusce iaculis est et est 
-                                    fermentum fringilla. Sed vestibulum arcu 
-                                    odio, at tristique nibh imperdiet vitae. 
-                                    Curabitur sed velit molestie leo mollis 
-                                    interdum sed id nunc. Sed sagittis 
-                                    scelerisque tincidunt.",
-        );
-        let text_to_find: ZSV = ZSV::from("et");
-        let loc = corpus.rfind_string(&text_to_find);
-        if let Some(loc) = loc {
-            if loc != 1180 {
-                return Err(());
-            } else {
-                return Ok(());
+        /// Returns `true` if every `()`, `[]`, and `{}` pair in the data is
+        /// matched and properly nested. Anything between single or double
+        /// quotes is skipped, and a backslash inside quotes escapes the next
+        /// character. A quote that is never closed makes the data unbalanced.
+        pub fn is_balanced(&self) -> bool {
+            let mut stack: Vec<char> = Vec::new();
+            let mut quote: Option<char> = None;
+            let mut chars = self.data.chars();
+            while let Some(c) = chars.next() {
+                if let Some(q) = quote {
+                    if c == '\\' {
+                        chars.next();
+                    } else if c == q {
+                        quote = None;
+                    }
+                    continue;
+                }
+                match c {
+                    '"' | '\'' => quote = Some(c),
+                    '(' => stack.push(')'),
+                    '[' => stack.push(']'),
+                    '{' => stack.push('}'),
+                    ')' | ']' | '}' if stack.pop() != Some(c) => return false,
+                    _ => {}
+                }
             }
-        } else {
-            return Err(());
+            stack.is_empty() && quote.is_none()
         }
-    }
 
-    #[test]
-    fn test_split_string() -> Result<(), ()> {
-        let data: ZSV = ZSV::from("Quick brown fox != lazy dog");
+        /// Returns the length of the data in UTF-16 code units, which is what
+        /// JavaScript's `String.length` reports. Characters outside the Basic
+        /// Multilingual Plane count as two units.
+        pub fn utf16_len(&self) -> usize {
+            self.data.chars().map(char::len_utf16).sum()
+        }
+
+        /// Returns the longest prefix of the data that fits within
+        /// `max_units` UTF-16 code units. Characters are never split, so a
+        /// character needing a surrogate pair is dropped entirely if only one
+        /// unit of room is left.
+        pub fn truncate_utf16(&self, max_units: usize) -> ZSV {
+            let mut units: usize = 0;
+            for (i, c) in self.data.char_indices() {
+                units += c.len_utf16();
+                if units > max_units {
+                    return ZSV::from(&self.data[..i]);
+                }
+            }
+            self.clone()
+        }
+
+        /// Computes the Shannon entropy of the character distribution in bits
+        /// per character. A string made of one repeated character scores
+        /// `0.0`, and `n` equally common distinct characters score
+        /// `log2(n)`. if the data is empty, we will return `0.0`.
+        pub fn shannon_entropy(&self) -> f64 {
+            let mut counts: HashMap<char, usize> = HashMap::new();
+            let mut total: usize = 0;
+            for c in self.data.chars() {
+                *counts.entry(c).or_insert(0) += 1;
+                total += 1;
+            }
+            if total == 0 {
+                return 0.0;
+            }
+            counts
+                .values()
+                .map(|&n| {
+                    let p: f64 = n as f64 / total as f64;
+                    -p * p.log2()
+                })
+                .sum()
+        }
+
+        /// Splits prose into sentences, each keeping its trailing whitespace,
+        /// so that concatenating the pieces reproduces the original data
+        /// exactly. A sentence ends at a run of `'.'`, `'!'`, or `'?'`
+        /// (optionally followed by closing quotes or brackets) that is
+        /// followed by whitespace or the end of the data; a terminator
+        /// directly followed by anything else, as in `"3.14"`, doesn't end
+        /// the sentence.
+        pub fn split_sentences_preserving(&self) -> Vec<ZSV> {
+            let mut sentences: Vec<ZSV> = Vec::new();
+            let chars: Vec<(usize, char)> = self.data.char_indices().collect();
+            let mut start: usize = 0;
+            let mut i: usize = 0;
+            while i < chars.len() {
+                if !matches!(chars[i].1, '.' | '!' | '?') {
+                    i += 1;
+                    continue;
+                }
+                let mut j: usize = i;
+                while j < chars.len() && matches!(chars[j].1, '.' | '!' | '?') {
+                    j += 1;
+                }
+                while j < chars.len() && matches!(chars[j].1, '"' | '\'' | ')' | ']' | '”' | '’')
+                {
+                    j += 1;
+                }
+                if j < chars.len() && !chars[j].1.is_whitespace() {
+                    i = j;
+                    continue;
+                }
+                while j < chars.len() && chars[j].1.is_whitespace() {
+                    j += 1;
+                }
+                let end: usize = chars.get(j).map_or(self.data.len(), |&(b, _)| b);
+                sentences.push(ZSV::from(&self.data[start..end]));
+                start = end;
+                i = j;
+            }
+            if start < self.data.len() {
+                sentences.push(ZSV::from(&self.data[start..]));
+            }
+            sentences
+        }
+
+        /// Classifies a single line of an INI-style file. The line is trimmed
+        /// first, and then recognized as a `Blank` line, a `Comment` starting
+        /// with `;` or `#`, a `[section]` header, or a `key = value` pair,
+        /// with the name, key, value, and comment text all trimmed too. The
+        /// value runs from the first `=` to the end of the line. A line that
+        /// is none of these, such as a bare `key`, is read as a key with an
+        /// empty value.
+        pub fn parse_ini_line(&self) -> IniLine {
+            let line: &str = self.data.trim();
+            if line.is_empty() {
+                return IniLine::Blank;
+            }
+            if let Some(comment) = line.strip_prefix([';', '#']) {
+                return IniLine::Comment(ZSV::from(comment.trim()));
+            }
+            if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                return IniLine::Section(ZSV::from(section.trim()));
+            }
+            match line.split_once('=') {
+                Some((key, value)) => {
+                    IniLine::KeyValue(ZSV::from(key.trim()), ZSV::from(value.trim()))
+                }
+                None => IniLine::KeyValue(ZSV::from(line), ZSV::from("")),
+            }
+        }
+
+        /// Returns a lazy iterator over the fields of a single CSV row
+        /// seperated by `delim`. A field that starts with `'"'` is quoted: it
+        /// may contain `delim` and newlines, a doubled `""` inside it stands
+        /// for one `'"'`, and the surrounding quotes are removed. Any text
+        /// between a closing quote and the next `delim` is kept as-is. Like
+        /// `str::split`, empty input yields a single empty field, and a
+        /// trailing `delim` yields a final empty field.
+        pub fn csv_fields(&self, delim: char) -> CsvFieldIter<'_> {
+            CsvFieldIter {
+                data: &self.data,
+                delim,
+                pos: 0,
+                done: false,
+            }
+        }
+
+        /// Splits a single CSV row on `delim` like `ZSV::csv_fields`, but also
+        /// records whether each field was quoted, so the row can be written
+        /// back out with its original quoting.
+        pub fn csv_fields_detailed(&self, delim: char) -> Vec<CsvField> {
+            let mut fields: CsvFieldIter = self.csv_fields(delim);
+            let mut out: Vec<CsvField> = Vec::new();
+            while let Some((value, was_quoted)) = fields.next_field() {
+                out.push(CsvField {
+                    value: ZSV::from(value),
+                    was_quoted,
+                });
+            }
+            out
+        }
+
+        /// Returns the data converted to the Unicode normalization `form`, so
+        /// that composed and decomposed spellings of the same text (such as
+        /// `"é"` as one code point, or as `"e"` plus a combining accent)
+        /// compare equal. Requires the `unicode-norm` feature.
+        #[cfg(feature = "unicode-norm")]
+        pub fn normalize(&self, form: NormalizationForm) -> ZSV {
+            use unicode_normalization::UnicodeNormalization;
+            let chars = self.data.chars();
+            ZSV::from(match form {
+                NormalizationForm::Nfc => chars.nfc().collect::<String>(),
+                NormalizationForm::Nfd => chars.nfd().collect::<String>(),
+                NormalizationForm::Nfkc => chars.nfkc().collect::<String>(),
+                NormalizationForm::Nfkd => chars.nfkd().collect::<String>(),
+            })
+        }
+
+        /// Splits a ZSV around the first instance of the seperator character
+        /// `c`, returning `Some((left, index, right))` where `index` is the
+        /// byte offset of the seperator in `self.data`, and neither `left`
+        /// nor `right` includes the seperator. if `c` doesn't exist within
+        /// `self.data`, we will return `None`.
+        pub fn split_char_with_index(&self, c: char) -> Option<(ZSV, usize, ZSV)> {
+            let indx: usize = self.data.find(c)?;
+            Some((
+                ZSV::from(&self.data[..indx]),
+                indx,
+                ZSV::from(&self.data[indx + c.len_utf8()..]),
+            ))
+        }
+
+        /// Returns the number of characters whose code point is above
+        /// `U+FFFF`, such as most emoji. These are the characters that take a
+        /// surrogate pair in UTF-16.
+        pub fn supplementary_char_count(&self) -> usize {
+            self.data.chars().filter(|&c| c as u32 > 0xFFFF).count()
+        }
+
+        /// Returns the number of terminal columns the data takes up when
+        /// rendered, where wide characters such as CJK ideographs take two
+        /// columns and control characters take none. Requires the `width`
+        /// feature.
+        #[cfg(feature = "width")]
+        pub fn display_width(&self) -> usize {
+            use unicode_width::UnicodeWidthChar;
+            self.data.chars().map(|c| c.width().unwrap_or(0)).sum()
+        }
+
+        /// Returns the longest prefix of the data whose display width (see
+        /// `display_width`) is at most `max_cols`. Characters are never
+        /// split, so a wide character that would only half fit is dropped.
+        /// Requires the `width` feature.
+        #[cfg(feature = "width")]
+        pub fn truncate_display_width(&self, max_cols: usize) -> ZSV {
+            use unicode_width::UnicodeWidthChar;
+            let mut cols: usize = 0;
+            for (i, c) in self.data.char_indices() {
+                cols += c.width().unwrap_or(0);
+                if cols > max_cols {
+                    return ZSV::from(&self.data[..i]);
+                }
+            }
+            self.clone()
+        }
+
+        /// Replaces the characters that are special in HTML (`&`, `<`, `>`,
+        /// `"`, and `'`) with entity references, so the result can be safely
+        /// embedded in HTML text or attribute values.
+        pub fn html_escape(&self) -> ZSV {
+            let mut out: String = String::with_capacity(self.data.len());
+            for c in self.data.chars() {
+                match c {
+                    '&' => out.push_str("&amp;"),
+                    '<' => out.push_str("&lt;"),
+                    '>' => out.push_str("&gt;"),
+                    '"' => out.push_str("&quot;"),
+                    '\'' => out.push_str("&#39;"),
+                    _ => out.push(c),
+                }
+            }
+            ZSV::from(out)
+        }
+
+        /// Reverses `html_escape`, decoding the named entities `&amp;`,
+        /// `&lt;`, `&gt;`, `&quot;`, `&apos;`, and `&nbsp;`, along with decimal
+        /// (`&#39;`) and hexadecimal (`&#x27;`) numeric references. Anything
+        /// that isn't a recognized entity, including a numeric reference to
+        /// an invalid code point or one written with a sign, is left as-is.
+        /// Only the first 10 bytes after each `&` are searched for the closing
+        /// `;`, which fits the longest entity (`&#x10FFFF;`), so numeric
+        /// references padded with extra leading zeros are left as-is too.
+        pub fn html_unescape(&self) -> ZSV {
+            const MAX_ENTITY_LEN: usize = 10;
+            let mut out: String = String::with_capacity(self.data.len());
+            let mut rest: &str = &self.data;
+            while let Some(amp) = rest.find('&') {
+                out.push_str(&rest[..amp]);
+                rest = &rest[amp..];
+                let semi: Option<usize> = rest.bytes().take(MAX_ENTITY_LEN).position(|b| b == b';');
+                let decoded: Option<(char, usize)> = semi.and_then(|semi| {
+                    let c: char = match &rest[1..semi] {
+                        "amp" => '&',
+                        "lt" => '<',
+                        "gt" => '>',
+                        "quot" => '"',
+                        "apos" => '\'',
+                        "nbsp" => '\u{a0}',
+                        entity => {
+                            let code: u32 = if let Some(hex) = entity
+                                .strip_prefix("#x")
+                                .or_else(|| entity.strip_prefix("#X"))
+                            {
+                                if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+                                    return None;
+                                }
+                                u32::from_str_radix(hex, 16).ok()?
+                            } else {
+                                let digits: &str = entity.strip_prefix('#')?;
+                                if !digits.bytes().all(|b| b.is_ascii_digit()) {
+                                    return None;
+                                }
+                                digits.parse().ok()?
+                            };
+                            char::from_u32(code)?
+                        }
+                    };
+                    Some((c, semi + 1))
+                });
+                match decoded {
+                    Some((c, len)) => {
+                        out.push(c);
+                        rest = &rest[len..];
+                    }
+                    None => {
+                        out.push('&');
+                        rest = &rest[1..];
+                    }
+                }
+            }
+            out.push_str(rest);
+            ZSV::from(out)
+        }
+
+        /// Finds whichever of `needles` occurs earliest in the data and splits
+        /// around it, returning `Some((left, needle_index, right))` where
+        /// `needle_index` is the position of the matching needle within
+        /// `needles`, and the needle itself is in neither half. When several
+        /// needles match at the same position, the one listed first wins.
+        /// Empty needles are ignored. if none of the needles occur, we will
+        /// return `None`.
+        pub fn split_on_first_of(&self, needles: &[ZSV]) -> Option<(ZSV, usize, ZSV)> {
+            let mut best: Option<(usize, usize)> = None;
+            for (n, needle) in needles.iter().enumerate() {
+                if needle.data.is_empty() {
+                    continue;
+                }
+                match (best, self.data.find(needle.data.as_str())) {
+                    (Some((b, _)), Some(i)) if b <= i => {}
+                    (_, Some(i)) => best = Some((i, n)),
+                    (_, None) => {}
+                }
+            }
+            let (i, n) = best?;
+            Some((
+                ZSV::from(&self.data[..i]),
+                n,
+                ZSV::from(&self.data[i + needles[n].data.len()..]),
+            ))
+        }
+
+        /// if the data starts with `open` and ends with `close`, returns what
+        /// is between them, so `"[abc]"` unwraps to `"abc"`. The two
+        /// delimiters must be distinct characters in the data, so a lone
+        /// `"\""` doesn't unwrap with `'"'` for both. Otherwise, we will
+        /// return `None`.
+        pub fn unwrap_delimiters(&self, open: char, close: char) -> Option<ZSV> {
+            self.data
+                .strip_prefix(open)
+                .and_then(|inner| inner.strip_suffix(close))
+                .map(ZSV::from)
+        }
+
+        /// Returns the length, in characters, of the longest consecutive run
+        /// of `c`. if `c` doesn't exist within `self.data`, we will return 0.
+        pub fn longest_run(&self, c: char) -> usize {
+            let mut best: usize = 0;
+            let mut run: usize = 0;
+            for d in self.data.chars() {
+                if d == c {
+                    run += 1;
+                    best = best.max(run);
+                } else {
+                    run = 0;
+                }
+            }
+            best
+        }
+
+        /// Returns the characters of the data as a `Vec<char>`. Building this
+        /// once and reusing it is cheaper than re-decoding the UTF-8 when a
+        /// caller needs repeated random access by character index.
+        pub fn to_chars(&self) -> Vec<char> {
+            self.data.chars().collect()
+        }
+
+        /// Builds a ZSV from a slice of characters; the inverse of
+        /// `to_chars`.
+        pub fn from_chars(chars: &[char]) -> ZSV {
+            ZSV::from(chars.iter().collect::<String>())
+        }
+
+        /// Parses a `"MAJOR.MINOR.PATCH"` version string with an optional
+        /// `"-prerelease"` tail, such as `"1.2.3"` or `"1.2.3-rc1"`, returning
+        /// the three numbers and the prerelease label (without its `'-'`).
+        /// Each number must be plain ASCII digits, and a prerelease label,
+        /// if present, must not be empty. Build metadata (`"+..."`) isn't
+        /// supported. For malformed input, we will return `None`.
+        pub fn parse_semver(&self) -> Option<(u64, u64, u64, Option<ZSV>)> {
+            let (version, pre) = match self.data.split_once('-') {
+                Some((_, "")) => return None,
+                Some((version, pre)) => (version, Some(ZSV::from(pre))),
+                None => (self.data.as_str(), None),
+            };
+            let number = |part: &str| -> Option<u64> {
+                if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+                    return None;
+                }
+                part.parse().ok()
+            };
+            let mut parts = version.split('.');
+            let major: u64 = number(parts.next()?)?;
+            let minor: u64 = number(parts.next()?)?;
+            let patch: u64 = number(parts.next()?)?;
+            if parts.next().is_some() {
+                return None;
+            }
+            Some((major, minor, patch, pre))
+        }
+
+        /// Returns the byte offset of the `n`-th `'\n'`, where `n` is 1-based.
+        /// Unlike `Iterator::nth`, `nth_newline_byte(1)` is the end of the
+        /// first line. if there are fewer than `n` newlines, or `n` is 0, we
+        /// will return `None`.
+        pub fn nth_newline_byte(&self, n: usize) -> Option<usize> {
+            self.data
+                .bytes()
+                .enumerate()
+                .filter(|&(_, b)| b == b'\n')
+                .nth(n.checked_sub(1)?)
+                .map(|(i, _)| i)
+        }
+
+        /// Splits the data into a flat list of `Token`s, scanning left to
+        /// right. Identifiers start with a letter or `'_'` and continue with
+        /// letters, digits, or `'_'`. Numbers are runs of ASCII digits, with
+        /// an optional `'.'` followed by more digits (so `"1.5"` is one number
+        /// but `"1."` is a number and a symbol). Runs of whitespace become a
+        /// single `Whitespace` token, and every other character is its own
+        /// `Symbol`. Concatenating the tokens reproduces the original data.
+        pub fn lex_simple(&self) -> Vec<Token> {
+            let chars: Vec<(usize, char)> = self.data.char_indices().collect();
+            let byte_at = |i: usize| -> usize { chars.get(i).map_or(self.data.len(), |&(b, _)| b) };
+            let mut tokens: Vec<Token> = Vec::new();
+            let mut i: usize = 0;
+            while i < chars.len() {
+                let c: char = chars[i].1;
+                let start: usize = i;
+                i += 1;
+                if c.is_alphabetic() || c == '_' {
+                    while i < chars.len() && (chars[i].1.is_alphanumeric() || chars[i].1 == '_') {
+                        i += 1;
+                    }
+                    tokens.push(Token::Ident(ZSV::from(
+                        &self.data[byte_at(start)..byte_at(i)],
+                    )));
+                } else if c.is_ascii_digit() {
+                    while i < chars.len() && chars[i].1.is_ascii_digit() {
+                        i += 1;
+                    }
+                    if i + 1 < chars.len() && chars[i].1 == '.' && chars[i + 1].1.is_ascii_digit() {
+                        i += 1;
+                        while i < chars.len() && chars[i].1.is_ascii_digit() {
+                            i += 1;
+                        }
+                    }
+                    tokens.push(Token::Number(ZSV::from(
+                        &self.data[byte_at(start)..byte_at(i)],
+                    )));
+                } else if c.is_whitespace() {
+                    while i < chars.len() && chars[i].1.is_whitespace() {
+                        i += 1;
+                    }
+                    tokens.push(Token::Whitespace(ZSV::from(
+                        &self.data[byte_at(start)..byte_at(i)],
+                    )));
+                } else {
+                    tokens.push(Token::Symbol(c));
+                }
+            }
+            tokens
+        }
+
+        /// Returns `true` if the data is a valid ASCII identifier: a letter
+        /// or `'_'`, followed by any number of letters, digits, or `'_'`. An
+        /// empty ZSV isn't a valid identifier.
+        pub fn is_valid_identifier(&self) -> bool {
+            let mut chars = self.data.chars();
+            matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+                && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+
+        /// Like `is_valid_identifier`, but any Unicode letter or digit is
+        /// accepted where the ASCII version accepts only ASCII ones, so
+        /// `"café"` and `"変数"` are valid.
+        pub fn is_valid_unicode_identifier(&self) -> bool {
+            let mut chars = self.data.chars();
+            matches!(chars.next(), Some(c) if c.is_alphabetic() || c == '_')
+                && chars.all(|c| c.is_alphanumeric() || c == '_')
+        }
+
+        /// Detects the column layout of an aligned text table from its
+        /// `header` line, returning the character position at which each
+        /// column starts: the first non-whitespace character, and every
+        /// non-whitespace character that follows whitespace. Header names
+        /// therefore must not contain whitespace themselves.
+        pub fn infer_columns(header: &ZSV) -> Vec<usize> {
+            let mut starts: Vec<usize> = Vec::new();
+            let mut prev_space: bool = true;
+            for (i, c) in header
+                .data
+                .trim_end_matches(['\r', '\n'])
+                .chars()
+                .enumerate()
+            {
+                if !c.is_whitespace() && prev_space {
+                    starts.push(i);
+                }
+                prev_space = c.is_whitespace();
+            }
+            starts
+        }
+
+        /// Splits a data row using column starts from `ZSV::infer_columns`,
+        /// trimming the padding around each cell. Anything before the first
+        /// column start is kept as part of the first cell. This is
+        /// `split_columns` with trimmed output.
+        pub fn split_by_inferred(&self, starts: &[usize]) -> Vec<ZSV> {
+            let mut starts: Vec<usize> = starts.to_vec();
+            if let Some(first) = starts.first_mut() {
+                *first = 0;
+            }
+            self.split_columns(&starts)
+                .into_iter()
+                .map(|cell| ZSV::from(cell.data.trim()))
+                .collect()
+        }
+
+        /// Computes a minimal list of character edits that turns `self` into
+        /// `other`, derived from the Levenshtein distance table. Walking the
+        /// script in order, `Keep` and `Delete` consume the next character of
+        /// `self`, while `Keep` and `Insert` produce the next character of
+        /// `other`. A substitution is written as a `Delete` followed by an
+        /// `Insert`.
+        pub fn edit_script(&self, other: &ZSV) -> Vec<EditOp> {
+            let a: Vec<char> = self.to_chars();
+            let b: Vec<char> = other.to_chars();
+            let width: usize = b.len() + 1;
+            // dist[i * width + j] is the edit distance between a[..i] and b[..j].
+            let mut dist: Vec<usize> = vec![0; (a.len() + 1) * width];
+            for i in 0..=a.len() {
+                for j in 0..=b.len() {
+                    dist[i * width + j] = if i == 0 {
+                        j
+                    } else if j == 0 {
+                        i
+                    } else {
+                        let substitute: usize = usize::from(a[i - 1] != b[j - 1]);
+                        (dist[(i - 1) * width + j - 1] + substitute)
+                            .min(dist[(i - 1) * width + j] + 1)
+                            .min(dist[i * width + j - 1] + 1)
+                    };
+                }
+            }
+            let mut script: Vec<EditOp> = Vec::new();
+            let (mut i, mut j) = (a.len(), b.len());
+            while i > 0 || j > 0 {
+                let here: usize = dist[i * width + j];
+                if i > 0 && j > 0 && a[i - 1] == b[j - 1] && here == dist[(i - 1) * width + j - 1] {
+                    script.push(EditOp::Keep(a[i - 1]));
+                    i -= 1;
+                    j -= 1;
+                } else if i > 0 && j > 0 && here == dist[(i - 1) * width + j - 1] + 1 {
+                    // Pushed in reverse, so this reads as Delete then Insert.
+                    script.push(EditOp::Insert(b[j - 1]));
+                    script.push(EditOp::Delete(a[i - 1]));
+                    i -= 1;
+                    j -= 1;
+                } else if i > 0 && here == dist[(i - 1) * width + j] + 1 {
+                    script.push(EditOp::Delete(a[i - 1]));
+                    i -= 1;
+                } else {
+                    script.push(EditOp::Insert(b[j - 1]));
+                    j -= 1;
+                }
+            }
+            script.reverse();
+            script
+        }
+
+        /// Splits a ZSV on every instance of `c` whose character index falls
+        /// within `range`, removing those seperators. Instances of `c`
+        /// outside `range` are ignored and stay in the surrounding text.
+        /// Like `str::split`, with no seperators in range we will return the
+        /// whole data as a single piece.
+        pub fn split_all_char_in_range(&self, c: char, range: Range<usize>) -> Vec<ZSV> {
+            let mut pieces: Vec<ZSV> = Vec::new();
+            let mut start: usize = 0;
+            for (n, (i, d)) in self.data.char_indices().enumerate() {
+                if d == c && range.contains(&n) {
+                    pieces.push(ZSV::from(&self.data[start..i]));
+                    start = i + d.len_utf8();
+                }
+            }
+            pieces.push(ZSV::from(&self.data[start..]));
+            pieces
+        }
+
+        /// Parses a comma-seperated list of numbers and dash ranges, such as
+        /// `"1-3,5,7-9"`, into inclusive ranges in the order written; a lone
+        /// number `n` becomes `n..=n`. Whitespace around each entry and
+        /// around the dash is ignored.
+        ///
+        /// # Errors
+        /// - `ParseError::Empty` if the input is empty or only whitespace.
+        /// - `ParseError::ReversedRange` for a reversed range like `"5-3"`.
+        /// - `ParseError::InvalidNumber` for a bad number or an empty entry,
+        ///   including the one left by a trailing comma as in `"1-3,"`.
+        pub fn parse_ranges(&self) -> Result<Vec<RangeInclusive<u64>>, ParseError> {
+            if self.data.trim().is_empty() {
+                return Err(ParseError::Empty);
+            }
+            let number = |s: &str| -> Result<u64, ParseError> {
+                let s: &str = s.trim();
+                if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(ParseError::InvalidNumber(ZSV::from(s)));
+                }
+                s.parse()
+                    .map_err(|_| ParseError::InvalidNumber(ZSV::from(s)))
+            };
+            let mut ranges: Vec<RangeInclusive<u64>> = Vec::new();
+            for entry in self.data.split(',') {
+                let range: RangeInclusive<u64> = match entry.split_once('-') {
+                    Some((start, end)) => {
+                        let (start, end) = (number(start)?, number(end)?);
+                        if start > end {
+                            return Err(ParseError::ReversedRange(ZSV::from(entry.trim())));
+                        }
+                        start..=end
+                    }
+                    None => {
+                        let n: u64 = number(entry)?;
+                        n..=n
+                    }
+                };
+                ranges.push(range);
+            }
+            Ok(ranges)
+        }
+
+        /// Returns the longest run of leading characters shared by every one
+        /// of `items`. if `items` is empty or they share no prefix, we will
+        /// return an empty ZSV.
+        pub fn common_prefix(items: &[ZSV]) -> ZSV {
+            let first: &ZSV = match items.first() {
+                Some(first) => first,
+                None => return ZSV::from(""),
+            };
+            let chars: usize = items[1..]
+                .iter()
+                .map(|item| first.common_prefix_len(item))
+                .min()
+                .unwrap_or_else(|| first.data.chars().count());
+            let end: usize = first
+                .data
+                .char_indices()
+                .nth(chars)
+                .map_or(first.data.len(), |(i, _)| i);
+            ZSV::from(&first.data[..end])
+        }
+
+        /// Parses the data into a tree of text runs and groups delimited by
+        /// `open` and `close`, so `"a(b(c))"` becomes
+        /// `Group([Text("a"), Group([Text("b"), Group([Text("c")])])])`. The
+        /// returned root is a `Group` holding the top-level nodes. Empty text
+        /// runs are left out. A `close` without a matching `open` is a
+        /// `ParseError::UnmatchedClose`, and an `open` that is never closed is
+        /// a `ParseError::UnclosedOpen`, each holding the byte offset of the
+        /// offending delimiter.
+        pub fn parse_nested(&self, open: char, close: char) -> Result<NestedNode, ParseError> {
+            fn group(
+                data: &str,
+                chars: &mut std::iter::Peekable<std::str::CharIndices<'_>>,
+                open: char,
+                close: char,
+                opened_at: Option<usize>,
+            ) -> Result<Vec<NestedNode>, ParseError> {
+                let mut nodes: Vec<NestedNode> = Vec::new();
+                let mut text_start: usize = opened_at.map_or(0, |i| i + open.len_utf8());
+                while let Some((i, c)) = chars.next() {
+                    if c != open && c != close {
+                        continue;
+                    }
+                    if text_start < i {
+                        nodes.push(NestedNode::Text(ZSV::from(&data[text_start..i])));
+                    }
+                    if c == open {
+                        nodes.push(NestedNode::Group(group(data, chars, open, close, Some(i))?));
+                        text_start = chars.peek().map_or(data.len(), |&(j, _)| j);
+                    } else if opened_at.is_some() {
+                        return Ok(nodes);
+                    } else {
+                        return Err(ParseError::UnmatchedClose(i));
+                    }
+                }
+                if let Some(i) = opened_at {
+                    return Err(ParseError::UnclosedOpen(i));
+                }
+                if text_start < data.len() {
+                    nodes.push(NestedNode::Text(ZSV::from(&data[text_start..])));
+                }
+                Ok(nodes)
+            }
+            let mut chars = self.data.char_indices().peekable();
+            Ok(NestedNode::Group(group(
+                &self.data, &mut chars, open, close, None,
+            )?))
+        }
+
+        /// Removes everything from `marker` to the end of the line on every
+        /// line, keeping the line endings. A `marker` between double quotes
+        /// doesn't start a comment, and a backslash inside quotes escapes the
+        /// next character. Only double quotes are recognized, so a `marker`
+        /// in single quotes, as in `'#'`, still starts a comment; this keeps
+        /// apostrophes in ordinary text from hiding comments. Quotes never
+        /// carry over from one line to the next. Text before a comment,
+        /// including any whitespace, is kept exactly as it was.
+        pub fn strip_line_comments(&self, marker: char) -> ZSV {
+            let mut out: String = String::with_capacity(self.data.len());
+            for line in self.data.split_inclusive('\n') {
+                let mut in_quotes: bool = false;
+                let mut escaped: bool = false;
+                let mut cut: Option<usize> = None;
+                for (i, c) in line.char_indices() {
+                    if escaped {
+                        escaped = false;
+                    } else if in_quotes && c == '\\' {
+                        escaped = true;
+                    } else if c == '"' {
+                        in_quotes = !in_quotes;
+                    } else if c == marker && !in_quotes {
+                        cut = Some(i);
+                        break;
+                    }
+                }
+                match cut {
+                    Some(i) => {
+                        out.push_str(&line[..i]);
+                        if line.ends_with("\r\n") {
+                            out.push_str("\r\n");
+                        } else if line.ends_with('\n') {
+                            out.push('\n');
+                        }
+                    }
+                    None => out.push_str(line),
+                }
+            }
+            ZSV::from(out)
+        }
+
+        /// Builds a pseudo-random string of `len` characters drawn from
+        /// `chars`, using a SplitMix64 generator seeded with `seed`. The same
+        /// seed, alphabet, and length always produce the same string, which
+        /// makes this useful for reproducible fuzzing. This is not suitable
+        /// for anything security-related. if `chars` is empty, we will return
+        /// an empty ZSV.
+        pub fn from_seeded_chars(seed: u64, chars: &[char], len: usize) -> ZSV {
+            if chars.is_empty() {
+                return ZSV::from("");
+            }
+            let mut state: u64 = seed;
+            let mut out: String = String::with_capacity(len);
+            for _ in 0..len {
+                state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+                let mut z: u64 = state;
+                z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+                z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+                z ^= z >> 31;
+                out.push(chars[(z % chars.len() as u64) as usize]);
+            }
+            ZSV::from(out)
+        }
+
+        /// Reads exactly `digits` leading ASCII digits as a decimal length,
+        /// returning that length along with everything that follows it. The
+        /// rest of the data is returned whole and isn't checked against the
+        /// length, so callers can decide how to handle short or long frames.
+        /// if `digits` is 0, any of the leading characters isn't an ASCII
+        /// digit, or the length doesn't fit in a `u64`, we will return
+        /// `None`.
+        pub fn strip_length_prefix(&self, digits: usize) -> Option<(u64, ZSV)> {
+            if digits == 0 {
+                return None;
+            }
+            let prefix: &str = self.data.get(..digits)?;
+            if !prefix.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            Some((prefix.parse().ok()?, ZSV::from(&self.data[digits..])))
+        }
+
+        /// Splits a ZSV on every instance of the seperator character `s`,
+        /// returning each field between them with the seperators removed.
+        /// This behaves like `str::split`: adjacent seperators produce an
+        /// empty field, a leading or trailing seperator produces an empty
+        /// first or last field, and empty input yields a single empty field.
+        pub fn split_all_char(&self, s: char) -> Vec<ZSV> {
+            self.as_view()
+                .split_all_char(s)
+                .into_iter()
+                .map(ZSV::from)
+                .collect()
+        }
+
+        /// Guesses which of `','`, `'\t'`, `';'`, or `'|'` seperates the
+        /// fields of a delimited file by looking at its first ten non-blank
+        /// lines. Each line is split quote-aware with `csv_fields`, and the
+        /// candidate for which the most lines share the same field count
+        /// (greater than one) wins; ties go to the candidate producing more
+        /// fields, then to the order listed above. if none of the candidates
+        /// ever splits a line, we will return `None`.
+        pub fn guess_delimiter(&self) -> Option<char> {
+            let lines: Vec<ZSV> = self
+                .data
+                .lines()
+                .filter(|l| !l.trim().is_empty())
+                .take(10)
+                .map(ZSV::from)
+                .collect();
+            let mut best: Option<(usize, usize, char)> = None;
+            for delim in [',', '\t', ';', '|'] {
+                let mut counts: HashMap<usize, usize> = HashMap::new();
+                for line in &lines {
+                    *counts.entry(line.csv_fields(delim).count()).or_insert(0) += 1;
+                }
+                let (fields, lines_agreeing) = counts
+                    .into_iter()
+                    .filter(|&(fields, _)| fields > 1)
+                    .max_by_key(|&(fields, n)| (n, fields))
+                    .unwrap_or((0, 0));
+                if lines_agreeing == 0 {
+                    continue;
+                }
+                match best {
+                    Some((n, f, _)) if (n, f) >= (lines_agreeing, fields) => {}
+                    _ => best = Some((lines_agreeing, fields, delim)),
+                }
+            }
+            best.map(|(_, _, delim)| delim)
+        }
+
+        /// Consumes the ZSV and returns its data as a `Box<str>`, releasing
+        /// any spare capacity the inner `String` was holding on to. This is
+        /// the more compact choice for storing many strings long-term.
+        pub fn into_boxed_str(self) -> Box<str> {
+            self.data.into_boxed_str()
+        }
+
+        /// Returns a copy of `self` with only the `n`th (1-based)
+        /// non-overlapping occurrence of `from` replaced by `to`. if there
+        /// are fewer than `n` occurrences, `n` is 0, or `from` is empty, we
+        /// will return the data unchanged.
+        pub fn replace_nth(&self, from: &ZSV, to: &ZSV, n: usize) -> ZSV {
+            if n == 0 || from.data.is_empty() {
+                return self.clone();
+            }
+            match self.data.match_indices(from.data.as_str()).nth(n - 1) {
+                Some((indx, _)) => {
+                    let mut out: String = String::with_capacity(self.data.len() + to.data.len());
+                    out.push_str(&self.data[..indx]);
+                    out.push_str(&to.data);
+                    out.push_str(&self.data[indx + from.data.len()..]);
+                    ZSV::from(out)
+                }
+                None => self.clone(),
+            }
+        }
+
+        /// Returns true if `self.data` begins with the character `c`.
+        pub fn starts_with_char(&self, c: char) -> bool {
+            self.data.starts_with(c)
+        }
+
+        /// Returns true if `self.data` begins with `other`. if `other` is
+        /// empty, we will always return true, and if `other` is longer than
+        /// `self`, we will return false. This shadows `str::starts_with`
+        /// reached through `Deref`, and only accepts a `&ZSV`; use
+        /// `(*zsv).starts_with(..)` for the other `str` patterns.
+        pub fn starts_with(&self, other: &ZSV) -> bool {
+            self.data.as_bytes().starts_with(other.data.as_bytes())
+        }
+
+        /// Returns true if `self.data` ends with the character `c`.
+        pub fn ends_with_char(&self, c: char) -> bool {
+            self.data.ends_with(c)
+        }
+
+        /// Returns true if `self.data` ends with `other`. if `other` is
+        /// empty, we will always return true, and if `other` is longer than
+        /// `self`, we will return false. Like `starts_with`, this shadows the
+        /// `str` method of the same name reached through `Deref`.
+        pub fn ends_with(&self, other: &ZSV) -> bool {
+            self.data.as_bytes().ends_with(other.data.as_bytes())
+        }
+
+        /// Computes a MinHash signature of `num_hashes` values over the
+        /// overlapping 3-character shingles of the data. The fraction of
+        /// positions where two signatures agree estimates the Jaccard
+        /// similarity of the two strings' shingle sets, so signatures can be
+        /// compared instead of the full text. Data shorter than 3 characters
+        /// is treated as a single shingle, and if the data is empty, every
+        /// value in the signature will be `u64::MAX`.
+        pub fn minhash(&self, num_hashes: usize) -> Vec<u64> {
+            const SHINGLE_LEN: usize = 3;
+            let chars: Vec<(usize, char)> = self.data.char_indices().collect();
+            let mut shingle_hashes: Vec<u64> = Vec::new();
+            if !chars.is_empty() && chars.len() < SHINGLE_LEN {
+                shingle_hashes.push(self.stable_hash());
+            }
+            for start in 0..chars.len().saturating_sub(SHINGLE_LEN - 1) {
+                let from: usize = chars[start].0;
+                let to: usize = chars
+                    .get(start + SHINGLE_LEN)
+                    .map_or(self.data.len(), |c| c.0);
+                shingle_hashes.push(ZSV::from(&self.data[from..to]).stable_hash());
+            }
+            (0..num_hashes as u64)
+                .map(|seed| {
+                    shingle_hashes
+                        .iter()
+                        .map(|h| {
+                            // Each seed gives an independent permutation by
+                            // running the shingle hash through a SplitMix64
+                            // finalizer.
+                            let mut z: u64 = h ^ seed.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+                            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+                            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+                            z ^ (z >> 31)
+                        })
+                        .min()
+                        .unwrap_or(u64::MAX)
+                })
+                .collect()
+        }
+
+        /// Returns true if the character `c` appears anywhere in `self.data`.
+        /// This stops scanning at the first match.
+        pub fn contains_char(&self, c: char) -> bool {
+            self.find_char(c).is_some()
+        }
+
+        /// Returns true if `other` appears anywhere in `self.data`. This
+        /// stops scanning at the first match. if `other` is empty, we will
+        /// always return true. This shadows `str::contains` reached through
+        /// `Deref`, and only accepts a `&ZSV`; use `(*zsv).contains(..)` for
+        /// the other `str` patterns.
+        pub fn contains(&self, other: &ZSV) -> bool {
+            self.find_string(other).is_some()
+        }
+
+        /// Returns the longest prefix of the data whose UTF-8 encoding fits
+        /// within `max_bytes` bytes. Characters are never split, so if even
+        /// the first character doesn't fit, we will return an empty ZSV.
+        pub fn prefix_within_bytes(&self, max_bytes: usize) -> ZSV {
+            if max_bytes >= self.data.len() {
+                return self.clone();
+            }
+            let mut end: usize = max_bytes;
+            while !self.data.is_char_boundary(end) {
+                end -= 1;
+            }
+            ZSV::from(&self.data[..end])
+        }
+
+        /// Returns, for each non-overlapping match of `needle`, the match
+        /// along with up to `context_chars` characters on either side of it,
+        /// clamped at the start and end of the data. if `needle` is empty, we
+        /// will return an empty `Vec`.
+        pub fn matches_with_context(&self, needle: &ZSV, context_chars: usize) -> Vec<ZSV> {
+            if needle.data.is_empty() {
+                return Vec::new();
+            }
+            self.data
+                .match_indices(needle.data.as_str())
+                .map(|(indx, m)| {
+                    let start: usize = self.data[..indx]
+                        .char_indices()
+                        .rev()
+                        .take(context_chars)
+                        .last()
+                        .map_or(indx, |(i, _)| i);
+                    let after: &str = &self.data[indx + m.len()..];
+                    let end: usize = indx
+                        + m.len()
+                        + after
+                            .chars()
+                            .take(context_chars)
+                            .map(char::len_utf8)
+                            .sum::<usize>();
+                    ZSV::from(&self.data[start..end])
+                })
+                .collect()
+        }
+
+        /// Returns a copy of the data with leading and trailing Unicode
+        /// whitespace removed, as with `str::trim`. This and the other
+        /// `trim*` methods shadow the `str` methods of the same names reached
+        /// through `Deref`, and return a ZSV rather than a `&str`; use
+        /// `(*zsv).trim()` to borrow instead.
+        pub fn trim(&self) -> ZSV {
+            ZSV::from(self.data.trim())
+        }
+
+        /// Returns a copy of the data with leading Unicode whitespace
+        /// removed, as with `str::trim_start`.
+        pub fn trim_start(&self) -> ZSV {
+            ZSV::from(self.data.trim_start())
+        }
+
+        /// Returns a copy of the data with trailing Unicode whitespace
+        /// removed, as with `str::trim_end`.
+        pub fn trim_end(&self) -> ZSV {
+            ZSV::from(self.data.trim_end())
+        }
+
+        /// Returns a copy of the data with every leading and trailing
+        /// instance of the character `c` removed.
+        pub fn trim_matches_char(&self, c: char) -> ZSV {
+            ZSV::from(self.data.trim_matches(c))
+        }
+
+        /// Interprets the escape sequences `\n`, `\t`, `\\`, `\"`, `\u{X}`
+        /// (one to six hex digits) and `\uXXXX` (exactly four hex digits) in
+        /// the data. A `\uXXXX` high surrogate followed by a `\uXXXX` low
+        /// surrogate is combined into one character, as in JSON. if any
+        /// escape is malformed, we will return an `UnescapeError` holding the
+        /// byte offset of its backslash.
+        pub fn unescape_unicode(&self) -> Result<ZSV, UnescapeError> {
+            let bytes: &[u8] = self.data.as_bytes();
+            let mut out: String = String::with_capacity(self.data.len());
+            let mut chars = self.data.char_indices();
+            // Reads a `\uXXXX` or `\u{X}` escape whose `u` is at byte `at`,
+            // returning the value and the byte offset just past the escape.
+            let read_unicode =
+                |start: usize, at: usize| -> Result<(u32, usize, bool), UnescapeError> {
+                    let (digits, next, braced) = if bytes.get(at + 1) == Some(&b'{') {
+                        let close: usize = self.data[at + 2..]
+                            .find('}')
+                            .map(|i| at + 2 + i)
+                            .ok_or(UnescapeError::MalformedUnicode(start))?;
+                        (&self.data[at + 2..close], close + 1, true)
+                    } else {
+                        (self.data.get(at + 1..at + 5).unwrap_or(""), at + 5, false)
+                    };
+                    let valid_len: bool = if braced {
+                        (1..=6).contains(&digits.len())
+                    } else {
+                        digits.len() == 4
+                    };
+                    if !valid_len || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+                        return Err(UnescapeError::MalformedUnicode(start));
+                    }
+                    let value: u32 = u32::from_str_radix(digits, 16)
+                        .map_err(|_| UnescapeError::MalformedUnicode(start))?;
+                    Ok((value, next, braced))
+                };
+            while let Some((i, c)) = chars.next() {
+                if c != '\\' {
+                    out.push(c);
+                    continue;
+                }
+                match chars.next() {
+                    Some((_, 'n')) => out.push('\n'),
+                    Some((_, 't')) => out.push('\t'),
+                    Some((_, '\\')) => out.push('\\'),
+                    Some((_, '"')) => out.push('"'),
+                    Some((at, 'u')) => {
+                        let (mut value, mut next, braced) = read_unicode(i, at)?;
+                        if !braced
+                            && (0xd800..0xdc00).contains(&value)
+                            && self.data[next..].starts_with("\\u")
+                        {
+                            if let Ok((low, after, false)) = read_unicode(next, next + 1) {
+                                if (0xdc00..0xe000).contains(&low) {
+                                    value = 0x10000 + ((value - 0xd800) << 10) + (low - 0xdc00);
+                                    next = after;
+                                }
+                            }
+                        }
+                        out.push(char::from_u32(value).ok_or(UnescapeError::InvalidCodePoint(i))?);
+                        // Everything in a valid escape is ASCII, so each
+                        // byte we skip over is exactly one char.
+                        for _ in at + 1..next {
+                            chars.next();
+                        }
+                    }
+                    _ => return Err(UnescapeError::UnknownEscape(i)),
+                }
+            }
+            Ok(ZSV::from(out))
+        }
+
+        /// Returns a copy of `self` with every non-overlapping occurrence of
+        /// `from` replaced by `to`, scanning left to right. if `from` is
+        /// empty, we will return the data unchanged. This shadows
+        /// `str::replace` reached through `Deref`, which would instead insert
+        /// `to` between every character for an empty `from`.
+        pub fn replace(&self, from: &ZSV, to: &ZSV) -> ZSV {
+            if from.data.is_empty() {
+                return self.clone();
+            }
+            ZSV::from(self.data.replace(from.data.as_str(), &to.data))
+        }
+
+        /// Returns a copy of `self` with every instance of the character
+        /// `from` replaced by `to`.
+        pub fn replace_char(&self, from: char, to: char) -> ZSV {
+            self.data
+                .chars()
+                .map(|c| if c == from { to } else { c })
+                .collect::<String>()
+                .into()
+        }
+
+        /// Returns the number of distinct lines in the data, comparing lines
+        /// by their exact content. Lines are split as with `str::lines`, so a
+        /// trailing newline doesn't add an extra empty line.
+        pub fn distinct_line_count(&self) -> usize {
+            self.data.lines().collect::<HashSet<&str>>().len()
+        }
+
+        /// Returns a case-folded copy of the data for use as a grouping or
+        /// dictionary key, so that strings differing only in case produce the
+        /// same key. The folding is Unicode lowercasing, plus the full case
+        /// folds that lowercasing misses: `ß` and `ẞ` become `"ss"`, final
+        /// `ς` becomes `σ`, long `ſ` becomes `s`, and the Latin ligatures
+        /// `ﬀ`..`ﬆ` are expanded. Both Turkish `İ` and `ı` fold to a plain
+        /// `i`, so `"İ"`, `"ı"`, `"i"`, and `"I"` all group together. The
+        /// key is meant for comparison, not for display.
+        pub fn to_case_fold_key(&self) -> ZSV {
+            let mut out: String = String::with_capacity(self.data.len());
+            for c in self.data.chars() {
+                match c {
+                    'ß' | 'ẞ' => out.push_str("ss"),
+                    'ς' => out.push('σ'),
+                    'ſ' => out.push('s'),
+                    'İ' | 'ı' => out.push('i'),
+                    'ﬀ' => out.push_str("ff"),
+                    'ﬁ' => out.push_str("fi"),
+                    'ﬂ' => out.push_str("fl"),
+                    'ﬃ' => out.push_str("ffi"),
+                    'ﬄ' => out.push_str("ffl"),
+                    'ﬅ' | 'ﬆ' => out.push_str("st"),
+                    _ => out.extend(c.to_lowercase()),
+                }
+            }
+            ZSV::from(out)
+        }
+
+        /// Returns true if `split_index(byte_idx)` would split the data into
+        /// two halves, meaning `byte_idx` is before the end of the data and
+        /// lies on a character boundary.
+        pub fn can_split_at_byte(&self, byte_idx: usize) -> bool {
+            byte_idx < self.data.len() && self.data.is_char_boundary(byte_idx)
+        }
+
+        /// A non-panicking version of `split_index`, returning the data left
+        /// of `byte_idx` and the data from `byte_idx` onwards. if `byte_idx`
+        /// is past the end of the data or falls inside a multibyte
+        /// character, we will return `None`.
+        pub fn safe_split_index(&self, byte_idx: usize) -> Option<(ZSV, ZSV)> {
+            if !self.can_split_at_byte(byte_idx) {
+                return None;
+            }
+            let (left, right) = self.data.split_at(byte_idx);
+            Some((ZSV::from(left), ZSV::from(right)))
+        }
+
+        /// Creates a ZSV from a `CString`, returning the `Utf8Error` if it
+        /// doesn't hold valid UTF-8 instead of panicking like the
+        /// `From<&CString>` impl does.
+        pub fn try_from_cstring(data: &CString) -> Result<ZSV, std::str::Utf8Error> {
+            data.to_str().map(ZSV::from)
+        }
+
+        /// Returns the length of the data in bytes. This is the unit used by
+        /// the `find_*`, `rfind_*`, and `split_*` methods.
+        pub fn len(&self) -> usize {
+            self.data.len()
+        }
+
+        /// Returns the number of `char`s in the data. This is the unit used
+        /// by the character-based methods, such as `split_middle`.
+        pub fn char_count(&self) -> usize {
+            self.data.chars().count()
+        }
+
+        /// Returns true if the data is empty.
+        pub fn is_empty(&self) -> bool {
+            self.data.is_empty()
+        }
+
+        /// Applies every `(from, to)` pair in `pairs` in a single left to
+        /// right pass. At each position the first pair in the list whose
+        /// `from` matches there wins, its `to` is written out, and scanning
+        /// resumes after the match, so replacements are never rescanned.
+        /// Pairs with an empty `from` are ignored. To prefer longer matches,
+        /// list the longer rules first.
+        pub fn replace_many(&self, pairs: &[(ZSV, ZSV)]) -> ZSV {
+            let mut out: String = String::with_capacity(self.data.len());
+            let mut rest: &str = &self.data;
+            'outter: while let Some(c) = rest.chars().next() {
+                for (from, to) in pairs {
+                    if !from.data.is_empty() && rest.starts_with(from.data.as_str()) {
+                        out.push_str(&to.data);
+                        rest = &rest[from.data.len()..];
+                        continue 'outter;
+                    }
+                }
+                out.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+            ZSV::from(out)
+        }
+
+        /// Returns a lazy iterator over the lines of the data, each without
+        /// its trailing `\n` or `\r\n`. Like `str::lines`, a trailing newline
+        /// doesn't produce a final empty line, and empty data yields no lines.
+        /// This shadows `str::lines` reached through `Deref`, yielding ZSVs
+        /// rather than `&str`s; use `(*zsv).lines()` to borrow instead.
+        pub fn lines(&self) -> impl Iterator<Item = ZSV> + '_ {
+            self.data.lines().map(ZSV::from)
+        }
+
+        /// Returns the characters of the data sorted by code point, so that
+        /// every anagram of a string shares the same signature. The
+        /// comparison is exact, so case and whitespace are significant.
+        pub fn sorted_char_signature(&self) -> ZSV {
+            let mut chars: Vec<char> = self.data.chars().collect();
+            chars.sort_unstable();
+            ZSV::from(chars.into_iter().collect::<String>())
+        }
+
+        /// Finds the first instance of `pat` in `self.data`, where `pat` can
+        /// be a `char`, a `&str`, or a `&ZSV`, and returns either
+        /// `Some(index)` with the byte offset of the match, or `None` if it
+        /// isn't found. This gives the same result as `find_char` or
+        /// `find_string` for the matching pattern type. This shadows
+        /// `str::find` reached through `Deref`, so closures and other `str`
+        /// patterns need `(*zsv).find(..)`.
+        pub fn find<P: ZSVPattern>(&self, pat: P) -> Option<usize> {
+            pat.find_in(self)
+        }
+
+        /// Splits the data into paragraphs, where paragraphs are separated by
+        /// runs of one or more blank lines, a blank line being one that is
+        /// empty or only whitespace. Each paragraph is returned as it appears
+        /// in the data, minus the line ending after its last line, and blank
+        /// lines before the first or after the last paragraph are dropped.
+        pub fn split_paragraphs(&self) -> Vec<ZSV> {
+            let mut out: Vec<ZSV> = Vec::new();
+            let mut start: Option<usize> = None;
+            let mut end: usize = 0;
+            let mut offset: usize = 0;
+            for line in self.data.split_inclusive('\n') {
+                if line.trim().is_empty() {
+                    if let Some(s) = start.take() {
+                        out.push(ZSV::from(&self.data[s..end]));
+                    }
+                } else {
+                    start.get_or_insert(offset);
+                    end = offset + line.trim_end_matches(['\r', '\n']).len();
+                }
+                offset += line.len();
+            }
+            if let Some(s) = start {
+                out.push(ZSV::from(&self.data[s..end]));
+            }
+            out
+        }
+
+        /// Returns the byte offset of every non-overlapping occurrence of
+        /// `needle`, scanning left to right. if `needle` is empty, we will
+        /// return an empty `Vec`.
+        pub fn find_all(&self, needle: &ZSV) -> Vec<usize> {
+            let mut out: Vec<usize> = Vec::new();
+            self.visit_matches(needle, |indx, _| out.push(indx));
+            out
+        }
+
+        /// Calls `f` with the byte offset and text of every non-overlapping
+        /// occurrence of `needle`, scanning left to right, without building
+        /// any result collection. if `needle` is empty, `f` is never called.
+        pub fn visit_matches<F: FnMut(usize, &ZSV)>(&self, needle: &ZSV, mut f: F) {
+            if needle.data.is_empty() {
+                return;
+            }
+            for (indx, _) in self.data.match_indices(needle.data.as_str()) {
+                f(indx, needle);
+            }
+        }
+
+        /// Splits the data on the first instance of the seperator character
+        /// `s`, returning the data before and after it with the seperator
+        /// itself dropped, as with `str::split_once`. if `s` isn't found, we
+        /// will return `None`.
+        pub fn split_once_char(&self, s: char) -> Option<(ZSV, ZSV)> {
+            self.data
+                .split_once(s)
+                .map(|(left, right)| (ZSV::from(left), ZSV::from(right)))
+        }
+
+        /// Finds the first instance of `c` at or after the byte offset
+        /// `start`, returning its absolute byte offset into `self.data`. if
+        /// `start` falls inside a multibyte character, scanning begins at the
+        /// next character, so `start = index + 1` can be used to step past a
+        /// match. if `start` is past the end of the data, we will return
+        /// `None`.
+        pub fn find_char_from(&self, c: char, start: usize) -> Option<usize> {
+            let start: usize = self.next_char_boundary(start)?;
+            ZSVRef::from(&self.data[start..])
+                .find_char(c)
+                .map(|indx| start + indx)
+        }
+
+        /// Finds the first instance of `f` at or after the byte offset
+        /// `start`, returning its absolute byte offset into `self.data`.
+        /// Offsets inside a multibyte character are handled as in
+        /// `find_char_from`. if `start` is past the end of the data, we will
+        /// return `None`.
+        pub fn find_string_from(&self, f: &ZSV, start: usize) -> Option<usize> {
+            let start: usize = self.next_char_boundary(start)?;
+            ZSVRef::from(&self.data[start..])
+                .find_string(&f.as_view())
+                .map(|indx| start + indx)
+        }
+
+        /// Returns the first character boundary at or after `indx`, or `None`
+        /// if `indx` is past the end of the data.
+        fn next_char_boundary(&self, indx: usize) -> Option<usize> {
+            (indx..=self.data.len()).find(|&i| self.data.is_char_boundary(i))
+        }
+
+        /// Returns the prefix function of the data's characters, which is
+        /// the failure table used by the Knuth-Morris-Pratt search behind
+        /// `find_string`. Entry `i` is the length, in characters, of the
+        /// longest proper prefix of the first `i + 1` characters that is
+        /// also a suffix of them.
+        pub fn prefix_function(&self) -> Vec<usize> {
+            kmp_prefix_table(&self.data.chars().collect::<Vec<char>>())
+        }
+
+        /// Returns the number of times the character `c` appears in the data.
+        pub fn count_char(&self, c: char) -> usize {
+            self.data.chars().filter(|&x| x == c).count()
+        }
+
+        /// Returns the number of non-overlapping occurrences of `needle`,
+        /// scanning left to right, so `"aa"` occurs twice in `"aaaa"`. This
+        /// matches the length of `find_all`, and so if `needle` is empty, we
+        /// will return 0.
+        pub fn count(&self, needle: &ZSV) -> usize {
+            let mut count: usize = 0;
+            self.visit_matches(needle, |_, _| count += 1);
+            count
+        }
+
+        /// Splits an email address list, such as the value of a `To:`
+        /// header, on commas that aren't inside a double-quoted display name
+        /// or an angle-bracketed address. Inside quotes, a backslash escapes
+        /// the next character. Each entry is trimmed of surrounding
+        /// whitespace, and entries left empty, as from a trailing comma, are
+        /// dropped.
+        pub fn split_address_list(&self) -> Vec<ZSV> {
+            let mut out: Vec<ZSV> = Vec::new();
+            let mut start: usize = 0;
+            let mut in_quotes: bool = false;
+            let mut in_angle: bool = false;
+            let mut escaped: bool = false;
+            let mut push = |entry: &str| {
+                let entry: &str = entry.trim();
+                if !entry.is_empty() {
+                    out.push(ZSV::from(entry));
+                }
+            };
+            for (i, c) in self.data.char_indices() {
+                if escaped {
+                    escaped = false;
+                    continue;
+                }
+                match c {
+                    '\\' if in_quotes => escaped = true,
+                    '"' if !in_angle => in_quotes = !in_quotes,
+                    '<' if !in_quotes => in_angle = true,
+                    '>' if !in_quotes => in_angle = false,
+                    ',' if !in_quotes && !in_angle => {
+                        push(&self.data[start..i]);
+                        start = i + 1;
+                    }
+                    _ => {}
+                }
+            }
+            push(&self.data[start..]);
+            out
+        }
+
+        /// Produces a unified diff of the lines of `self` against the lines
+        /// of `other`, in the format used by `diff -u` and `git diff`. Each
+        /// hunk starts with an `@@ -start,len +start,len @@` header and
+        /// shows changed lines prefixed with `-` or `+`, surrounded by up to
+        /// `context` unchanged lines prefixed with a space. Hunks whose
+        /// context would overlap are merged. The `---`/`+++` file header is
+        /// left to the caller, and lines are split as with `str::lines`, so
+        /// line endings aren't compared. if the lines are identical, we will
+        /// return an empty ZSV.
+        pub fn unified_diff(&self, other: &ZSV, context: usize) -> ZSV {
+            let a: Vec<&str> = self.data.lines().collect();
+            let b: Vec<&str> = other.data.lines().collect();
+            let width: usize = b.len() + 1;
+            // lcs[i * width + j] is the length of the longest common
+            // subsequence of a[i..] and b[j..].
+            let mut lcs: Vec<usize> = vec![0; (a.len() + 1) * width];
+            for i in (0..a.len()).rev() {
+                for j in (0..b.len()).rev() {
+                    lcs[i * width + j] = if a[i] == b[j] {
+                        lcs[(i + 1) * width + j + 1] + 1
+                    } else {
+                        lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+                    };
+                }
+            }
+            // Each op is (prefix, line, index into a, index into b), where the
+            // indices are the positions before the op is applied.
+            let mut ops: Vec<(char, &str, usize, usize)> = Vec::new();
+            let (mut i, mut j) = (0usize, 0usize);
+            while i < a.len() || j < b.len() {
+                if i < a.len() && j < b.len() && a[i] == b[j] {
+                    ops.push((' ', a[i], i, j));
+                    i += 1;
+                    j += 1;
+                } else if j == b.len()
+                    || (i < a.len() && lcs[(i + 1) * width + j] >= lcs[i * width + j + 1])
+                {
+                    ops.push(('-', a[i], i, j));
+                    i += 1;
+                } else {
+                    ops.push(('+', b[j], i, j));
+                    j += 1;
+                }
+            }
+            let changes: Vec<usize> = (0..ops.len()).filter(|&k| ops[k].0 != ' ').collect();
+            // Formats one side of a hunk header, where an empty side names the
+            // line before it.
+            let range = |start: usize, len: usize| match len {
+                0 => format!("{},0", start),
+                1 => format!("{}", start + 1),
+                _ => format!("{},{}", start + 1, len),
+            };
+            let mut out: String = String::new();
+            let mut k: usize = 0;
+            while k < changes.len() {
+                let start: usize = changes[k].saturating_sub(context);
+                let mut end: usize = (changes[k] + context + 1).min(ops.len());
+                while k + 1 < changes.len() && changes[k + 1] <= end + context {
+                    k += 1;
+                    end = (changes[k] + context + 1).min(ops.len());
+                }
+                k += 1;
+                let hunk: &[(char, &str, usize, usize)] = &ops[start..end];
+                let a_len: usize = hunk.iter().filter(|op| op.0 != '+').count();
+                let b_len: usize = hunk.iter().filter(|op| op.0 != '-').count();
+                out.push_str(&format!(
+                    "@@ -{} +{} @@\n",
+                    range(hunk[0].2, a_len),
+                    range(hunk[0].3, b_len)
+                ));
+                for (prefix, line, _, _) in hunk {
+                    out.push(*prefix);
+                    out.push_str(line);
+                    out.push('\n');
+                }
+            }
+            ZSV::from(out)
+        }
+
+        /// Returns the characters from char index `start` up to but not
+        /// including char index `end`. These are character counts rather
+        /// than byte offsets, so multibyte characters are never cut. if
+        /// `start > end` or `end` is past `char_count()`, we will return
+        /// `None`.
+        pub fn substring(&self, start: usize, end: usize) -> Option<ZSV> {
+            if start > end {
+                return None;
+            }
+            // Byte offsets of every char boundary, including the end.
+            let mut boundaries = self
+                .data
+                .char_indices()
+                .map(|(i, _)| i)
+                .chain([self.data.len()]);
+            let from: usize = boundaries.nth(start)?;
+            let to: usize = if end == start {
+                from
+            } else {
+                boundaries.nth(end - start - 1)?
+            };
+            Some(ZSV::from(&self.data[from..to]))
+        }
+
+        /// Returns true if the whole of the data matches the glob `pattern`,
+        /// where `*` matches any run of characters (including none), `?`
+        /// matches exactly one character, and every other character matches
+        /// itself. There's no escaping, and `/` isn't treated specially.
+        pub fn matches_glob(&self, pattern: &ZSV) -> bool {
+            let text: Vec<char> = self.to_chars();
+            let pat: Vec<char> = pattern.to_chars();
+            let (mut t, mut p) = (0usize, 0usize);
+            // The pattern position just after the last `*`, and the text
+            // position it was tried at, so a failed match can backtrack by
+            // letting that `*` swallow one more character.
+            let mut star: Option<(usize, usize)> = None;
+            while t < text.len() {
+                if p < pat.len() && (pat[p] == '?' || (pat[p] != '*' && pat[p] == text[t])) {
+                    t += 1;
+                    p += 1;
+                } else if p < pat.len() && pat[p] == '*' {
+                    p += 1;
+                    star = Some((p, t));
+                } else if let Some((star_p, star_t)) = star {
+                    p = star_p;
+                    t = star_t + 1;
+                    star = Some((star_p, star_t + 1));
+                } else {
+                    return false;
+                }
+            }
+            pat[p..].iter().all(|&c| c == '*')
+        }
+
+        /// Joins every ZSV in `parts`, in order, into one new ZSV.
+        pub fn concat(parts: &[ZSV]) -> ZSV {
+            let mut out: String = String::with_capacity(parts.iter().map(|p| p.data.len()).sum());
+            for part in parts {
+                out.push_str(&part.data);
+            }
+            ZSV::from(out)
+        }
+
+        /// Joins every ZSV in `parts` into one new ZSV, with `sep` between
+        /// each pair of parts and no trailing seperator. This undoes
+        /// `split_all_char`, so an empty slice gives an empty ZSV and a
+        /// single part is returned unchanged.
+        pub fn join(parts: &[ZSV], sep: &ZSV) -> ZSV {
+            let mut out: String = String::new();
+            for (i, part) in parts.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(&sep.data);
+                }
+                out.push_str(&part.data);
+            }
+            ZSV::from(out)
+        }
+
+        /// Joins every ZSV in `parts` with the seperator character `sep`, as
+        /// with `join`.
+        pub fn join_char(parts: &[ZSV], sep: char) -> ZSV {
+            ZSV::join(parts, &ZSV::from(sep.to_string()))
+        }
+
+        /// Expands shell-style brace groups, so `"a{b,c}d"` becomes
+        /// `["abd", "acd"]`. Groups may be nested, and several groups in one
+        /// string expand to every combination, in order. A brace without a
+        /// matching partner, or a group with no top-level comma such as
+        /// `"{a}"`, is malformed and is kept as literal text, matching shells.
+        pub fn expand_braces(&self) -> Vec<ZSV> {
+            // Finds the first well formed group, returning the byte offsets
+            // of its braces and of the commas at its top level.
+            let find_group = |data: &str| -> Option<(usize, usize, Vec<usize>)> {
+                for (open, _) in data.match_indices('{') {
+                    let mut depth: usize = 0;
+                    let mut commas: Vec<usize> = Vec::new();
+                    for (i, c) in data[open..].char_indices() {
+                        match c {
+                            '{' => depth += 1,
+                            '}' => depth -= 1,
+                            ',' if depth == 1 => commas.push(open + i),
+                            _ => {}
+                        }
+                        if depth == 0 {
+                            if !commas.is_empty() {
+                                return Some((open, open + i, commas));
+                            }
+                            break;
+                        }
+                    }
+                }
+                None
+            };
+            let (open, close, commas) = match find_group(&self.data) {
+                Some(group) => group,
+                None => return vec![self.clone()],
+            };
+            let prefix: &str = &self.data[..open];
+            let suffix: &str = &self.data[close + 1..];
+            let mut starts: Vec<usize> = vec![open + 1];
+            starts.extend(commas.iter().map(|c| c + 1));
+            let mut ends: Vec<usize> = commas;
+            ends.push(close);
+            let mut out: Vec<ZSV> = Vec::new();
+            for (start, end) in starts.into_iter().zip(ends) {
+                let rest: ZSV = ZSV::from(format!("{}{}", &self.data[start..end], suffix));
+                for expanded in rest.expand_braces() {
+                    out.push(ZSV::from(format!("{}{}", prefix, expanded.data)));
+                }
+            }
+            out
+        }
+
+        /// Returns a lowercased copy of the data, using Unicode's rules as
+        /// with `str::to_lowercase`. This shadows the `str` method of the
+        /// same name reached through `Deref`, and returns a ZSV rather than
+        /// a `String`; use `(*zsv).to_lowercase()` to call the `str` one.
+        pub fn to_lowercase(&self) -> ZSV {
+            ZSV::from(self.data.to_lowercase())
+        }
+
+        /// Returns an uppercased copy of the data, using Unicode's rules as
+        /// with `str::to_uppercase`. Like `to_lowercase`, this shadows the
+        /// `str` method of the same name and returns a ZSV.
+        pub fn to_uppercase(&self) -> ZSV {
+            ZSV::from(self.data.to_uppercase())
+        }
+
+        /// Finds the first instance of `needle` in `self.data` while treating
+        /// ASCII letters as equal regardless of case, and returns either
+        /// `Some(index)` with the byte offset of the match in the original
+        /// data, or `None`. Only ASCII is folded, so `"É"` won't match `"é"`.
+        /// No lowercased copy of the data is made. if `needle` is empty, we
+        /// will always return `Some(0)`.
+        pub fn find_ignore_ascii_case(&self, needle: &ZSV) -> Option<usize> {
+            if needle.data.is_empty() {
+                return Some(0);
+            }
+            // ASCII case folding never maps onto a UTF-8 continuation byte,
+            // so every match lands on a character boundary.
+            self.data
+                .as_bytes()
+                .windows(needle.data.len())
+                .position(|window| window.eq_ignore_ascii_case(needle.data.as_bytes()))
+        }
+
+        /// Returns the Unicode scalar value of every character in the data,
+        /// in order.
+        pub fn code_points(&self) -> Vec<u32> {
+            self.data.chars().map(u32::from).collect()
+        }
+
+        /// Replaces typographic punctuation with plain ASCII: curly double
+        /// quotes (`“ ” „ ‟`) become `"`, curly single quotes and apostrophes
+        /// (`‘ ’ ‚ ‛`) become `'`, an en dash (`–`) becomes `-`, and an em
+        /// dash (`—`) becomes `--`. Everything else is left unchanged.
+        pub fn normalize_quotes(&self) -> ZSV {
+            let mut out: String = String::with_capacity(self.data.len());
+            for c in self.data.chars() {
+                match c {
+                    '\u{201c}' | '\u{201d}' | '\u{201e}' | '\u{201f}' => out.push('"'),
+                    '\u{2018}' | '\u{2019}' | '\u{201a}' | '\u{201b}' => out.push('\''),
+                    '\u{2013}' => out.push('-'),
+                    '\u{2014}' => out.push_str("--"),
+                    _ => out.push(c),
+                }
+            }
+            ZSV::from(out)
+        }
+    }
+
+    impl From<&str> for ZSV {
+        fn from(data: &str) -> ZSV {
+            ZSV {
+                data: data.to_owned(),
+            }
+        }
+    }
+    impl From<String> for ZSV {
+        fn from(data: String) -> ZSV {
+            ZSV { data: data.clone() }
+        }
+    }
+    /// # Panics
+    /// Panics if `data` isn't valid UTF-8. Use `ZSV::try_from_cstring` for
+    /// C strings that may hold arbitrary bytes.
+    impl From<&CString> for ZSV {
+        fn from(data: &CString) -> ZSV {
+            ZSV {
+                data: data
+                    .to_str()
+                    .expect("Invalid UTF-8 provided to ZSV::From<&CString>()")
+                    .to_owned(),
+            }
+        }
+    }
+
+    impl std::fmt::Display for ZSV {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.pad(&self.data)
+        }
+    }
+
+    /// Borrows the data as a `&str`, for APIs taking `impl AsRef<str>`. Use
+    /// `ZSV::as_view` for a borrowed `ZSVRef` instead.
+    impl AsRef<str> for ZSV {
+        fn as_ref(&self) -> &str {
+            &self.data
+        }
+    }
+
+    /// This lets a `HashMap` or `BTreeMap` keyed by ZSV be queried with a
+    /// `&str`, since ZSV hashes and orders exactly like its data.
+    impl std::borrow::Borrow<str> for ZSV {
+        fn borrow(&self) -> &str {
+            &self.data
+        }
+    }
+
+    impl std::ops::Add for ZSV {
+        type Output = ZSV;
+
+        fn add(mut self, other: ZSV) -> ZSV {
+            self.data.push_str(&other.data);
+            self
+        }
+    }
+
+    impl std::ops::AddAssign for ZSV {
+        fn add_assign(&mut self, other: ZSV) {
+            self.data.push_str(&other.data);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl serde::Serialize for ZSV {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(&self.data)
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl<'de> serde::Deserialize<'de> for ZSV {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<ZSV, D::Error> {
+            String::deserialize(deserializer).map(ZSV::from)
+        }
+    }
+
+    impl std::ops::Deref for ZSV {
+        type Target = str;
+
+        fn deref(&self) -> &str {
+            &self.data
+        }
+    }
+
+    /// The unit of indentation used by a block of text, as reported by
+    /// `ZSV::detect_indent`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum IndentStyle {
+        Tabs,
+        Spaces(usize),
+    }
+
+    /// The error returned by the `parse_*` family of methods on `ZSV`.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum ParseError {
+        /// The input was empty, or contained only whitespace.
+        Empty,
+        /// The numeric part of the input wasn't a valid number, or was too
+        /// large to represent.
+        InvalidNumber(ZSV),
+        /// The unit suffix of the input wasn't recognized.
+        InvalidUnit(ZSV),
+        /// A range's start was greater than its end, as in `"5-3"`.
+        ReversedRange(ZSV),
+        /// A closing delimiter had no matching opening delimiter. Holds the
+        /// byte offset of the closing delimiter.
+        UnmatchedClose(usize),
+        /// An opening delimiter was never closed. Holds the byte offset of
+        /// the opening delimiter.
+        UnclosedOpen(usize),
+    }
+
+    impl std::fmt::Display for ParseError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                ParseError::Empty => write!(f, "cannot parse an empty string"),
+                ParseError::InvalidNumber(n) => write!(f, "invalid number: {:?}", n.data),
+                ParseError::InvalidUnit(u) => write!(f, "invalid unit: {:?}", u.data),
+                ParseError::ReversedRange(r) => {
+                    write!(f, "range start is after its end: {:?}", r.data)
+                }
+                ParseError::UnmatchedClose(i) => {
+                    write!(f, "unmatched closing delimiter at byte {}", i)
+                }
+                ParseError::UnclosedOpen(i) => {
+                    write!(f, "unclosed opening delimiter at byte {}", i)
+                }
+            }
+        }
+    }
+
+    impl std::error::Error for ParseError {}
+
+    /// The error returned by `ZSV::unescape_unicode`. Each variant holds the
+    /// byte offset of the backslash that starts the bad escape.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum UnescapeError {
+        /// A backslash was followed by an unrecognized character, or by
+        /// nothing at all.
+        UnknownEscape(usize),
+        /// A `\u` escape wasn't followed by four hex digits or by one to six
+        /// hex digits in braces.
+        MalformedUnicode(usize),
+        /// A `\u` escape named a value that isn't a Unicode scalar value,
+        /// such as an unpaired surrogate.
+        InvalidCodePoint(usize),
+    }
+
+    impl std::fmt::Display for UnescapeError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                UnescapeError::UnknownEscape(i) => write!(f, "unknown escape at byte {}", i),
+                UnescapeError::MalformedUnicode(i) => {
+                    write!(f, "malformed unicode escape at byte {}", i)
+                }
+                UnescapeError::InvalidCodePoint(i) => {
+                    write!(f, "invalid code point in escape at byte {}", i)
+                }
+            }
+        }
+    }
+
+    impl std::error::Error for UnescapeError {}
+
+    /// A table of the byte offsets at which each line of a ZSV starts, built
+    /// by `ZSV::build_line_map`, for converting between byte offsets and
+    /// zero-based `(line, column)` positions. Columns are measured in bytes
+    /// from the start of the line.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct LineMap {
+        line_starts: Vec<usize>,
+        len: usize,
+    }
+
+    impl LineMap {
+        /// Returns the number of lines in the map. Data ending in a newline
+        /// has a final, empty line after it.
+        pub fn line_count(&self) -> usize {
+            self.line_starts.len()
+        }
+
+        /// Converts a byte offset into a `(line, column)` position. The offset
+        /// one past the last byte is valid and maps to the end of the final
+        /// line. if `byte` is beyond that, we will return `None`.
+        pub fn byte_to_position(&self, byte: usize) -> Option<(usize, usize)> {
+            if byte > self.len {
+                return None;
+            }
+            let line: usize = self.line_starts.partition_point(|&start| start <= byte) - 1;
+            Some((line, byte - self.line_starts[line]))
+        }
+
+        /// Converts a `(line, column)` position back into a byte offset. if
+        /// `line` doesn't exist, or `col` runs past the end of that line
+        /// (including its newline), we will return `None`.
+        pub fn position_to_byte(&self, line: usize, col: usize) -> Option<usize> {
+            let start: usize = *self.line_starts.get(line)?;
+            let byte: usize = start.checked_add(col)?;
+            match self.line_starts.get(line + 1) {
+                Some(&next) if byte < next => Some(byte),
+                None if byte <= self.len => Some(byte),
+                _ => None,
+            }
+        }
+    }
+
+    /// A single classified line of an INI-style file, as returned by
+    /// `ZSV::parse_ini_line`.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum IniLine {
+        /// A `[section]` header, holding the section name.
+        Section(ZSV),
+        /// A `key = value` pair.
+        KeyValue(ZSV, ZSV),
+        /// A `;` or `#` comment, holding the text after the marker.
+        Comment(ZSV),
+        /// A line that is empty or only whitespace.
+        Blank,
+    }
+
+    /// A lazy iterator over the fields of a single CSV row, created by
+    /// `ZSV::csv_fields`.
+    #[derive(Debug, Clone)]
+    pub struct CsvFieldIter<'a> {
+        data: &'a str,
+        delim: char,
+        pos: usize,
+        done: bool,
+    }
+
+    impl<'a> CsvFieldIter<'a> {
+        /// Reads the next field, returning its unescaped contents and whether
+        /// it was quoted.
+        fn next_field(&mut self) -> Option<(String, bool)> {
+            if self.done {
+                return None;
+            }
+            let rest: &str = &self.data[self.pos..];
+            let mut field: String = String::new();
+            let mut quoted: bool = false;
+            let mut in_quotes: bool = false;
+            let mut chars = rest.char_indices().peekable();
+            if rest.starts_with('"') {
+                quoted = true;
+                in_quotes = true;
+                chars.next();
+            }
+            while let Some((i, c)) = chars.next() {
+                if in_quotes {
+                    if c == '"' {
+                        if chars.peek().map(|&(_, n)| n) == Some('"') {
+                            field.push('"');
+                            chars.next();
+                        } else {
+                            in_quotes = false;
+                        }
+                    } else {
+                        field.push(c);
+                    }
+                } else if c == self.delim {
+                    self.pos += i + c.len_utf8();
+                    return Some((field, quoted));
+                } else {
+                    field.push(c);
+                }
+            }
+            self.done = true;
+            Some((field, quoted))
+        }
+    }
+
+    impl<'a> Iterator for CsvFieldIter<'a> {
+        type Item = ZSV;
+
+        fn next(&mut self) -> Option<ZSV> {
+            self.next_field().map(|(field, _)| ZSV::from(field))
+        }
+    }
+
+    /// A single CSV field along with whether it was quoted in the source,
+    /// as returned by `ZSV::csv_fields_detailed`.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct CsvField {
+        /// The field's value, with quotes removed and `""` unescaped.
+        pub value: ZSV,
+        /// Whether the field was wrapped in double quotes.
+        pub was_quoted: bool,
+    }
+
+    /// The Unicode normalization forms accepted by `ZSV::normalize`.
+    #[cfg(feature = "unicode-norm")]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum NormalizationForm {
+        /// Canonical decomposition followed by canonical composition.
+        Nfc,
+        /// Canonical decomposition.
+        Nfd,
+        /// Compatibility decomposition followed by canonical composition.
+        Nfkc,
+        /// Compatibility decomposition.
+        Nfkd,
+    }
+
+    /// A token produced by `ZSV::lex_simple`.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum Token {
+        /// A letter or `'_'` followed by any letters, digits, or `'_'`.
+        Ident(ZSV),
+        /// A run of ASCII digits, optionally with a fractional part.
+        Number(ZSV),
+        /// Any other single non-whitespace character.
+        Symbol(char),
+        /// A run of whitespace.
+        Whitespace(ZSV),
+    }
+
+    /// A single step of an edit script produced by `ZSV::edit_script`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum EditOp {
+        /// Keep this character from the source.
+        Keep(char),
+        /// Insert this character.
+        Insert(char),
+        /// Delete this character from the source.
+        Delete(char),
+    }
+
+    /// A node of the tree built by `ZSV::parse_nested`.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum NestedNode {
+        /// A run of text containing no delimiters.
+        Text(ZSV),
+        /// A delimited group, holding its contents in order. The root of the
+        /// tree is also a `Group`.
+        Group(Vec<NestedNode>),
+    }
+
+    /// A borrowed, zero-copy view of a string, with the same searching and
+    /// splitting API as `ZSV`. Every `ZSVRef` returned by its methods
+    /// borrows from the original buffer, so no allocations are made for the
+    /// pieces. Indices are byte offsets, as with `ZSV`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    pub struct ZSVRef<'a> {
+        pub data: &'a str,
+    }
+
+    impl<'a> ZSVRef<'a> {
+        /// Finds the first instance of `f` in `self.data`, and returns either
+        /// `Some(index)` with the byte offset of the character, or `None` if
+        /// the character doesn't exist within `self.data`.
+        pub fn find_char(&self, f: char) -> Option<usize> {
+            self.data.find(f)
+        }
+
+        /// Finds the first instance of `f` in `self.data`, and returns either
+        /// `Some(index)` with the byte offset of the match, or `None` if the
+        /// string isn't contained within `self.data`. if `f.data.len()` is 0,
+        /// we will always return `Some(0)`
+        pub fn find_string(&self, f: &ZSVRef<'_>) -> Option<usize> {
+            if f.data.len() > self.data.len() {
+                return None;
+            }
+            if f.data.is_empty() {
+                return Some(0usize);
+            }
+            // Matching whole UTF-8 sequences byte by byte can only ever match
+            // at a character boundary, so there's no need to decode chars.
+            kmp_find(self.data.bytes(), f.data.as_bytes())
+        }
+
+        /// Finds the last instance of `f` in `self.data`, and returns either
+        /// `Some(index)` with the byte offset of the character, or `None` if
+        /// the character doesn't exist within `self.data`. This matches
+        /// `str::rfind`.
+        pub fn rfind_char(&self, f: char) -> Option<usize> {
+            for (indx, c) in self.data.char_indices().rev() {
+                if c == f {
+                    return Some(indx);
+                }
+            }
+            None
+        }
+
+        /// Finds the last instance of `f` in `self.data`, and returns either
+        /// `Some(index)` with the byte offset of the match, or `None` if the
+        /// string isn't contained within `self.data`. if `f.data.len()` is 0,
+        /// we will always return the byte offset of the last character. if
+        /// `self.data.len()` is 0, we will return None.
+        pub fn rfind_string(&self, f: &ZSVRef<'_>) -> Option<usize> {
+            if f.data.len() > self.data.len() {
+                return None;
+            }
+            if self.data.is_empty() {
+                return None;
+            }
+            if f.data.is_empty() {
+                return self.data.char_indices().next_back().map(|(i, _)| i);
+            }
+            // Searching the reversed haystack for the reversed needle finds
+            // the last match first; we then map its position back.
+            let reversed: Vec<u8> = f.data.bytes().rev().collect();
+            kmp_find(self.data.bytes().rev(), &reversed)
+                .map(|indx| self.data.len() - indx - reversed.len())
+        }
+
+        /// Splits a view into a tuple of `(a, b)` where `Some(a)` is all data
+        /// left of the first instance of the seperator character `s`, and
+        /// `Some(b)` is all data to the right of and including the seperator
+        /// character `s`. Both halves borrow from the original buffer.
+        pub fn split_char(&self, s: char) -> (Option<ZSVRef<'a>>, Option<ZSVRef<'a>>) {
+            if let Some(seperator) = self.find_char(s) {
+                self.split_index(seperator)
+            } else {
+                (Some(*self), None)
+            }
+        }
+
+        /// Splits a view into a tuple of `(a, b)` where `Some(a)` is all data
+        /// left of the byte offset `indx`, and `Some(b)` is all data to the
+        /// right of and including `indx`. if `indx` is past the end of the
+        /// data, we will return `(None, None)`.
+        ///
+        /// # Panics
+        /// Panics if `indx` is not on a character boundary.
+        pub fn split_index(&self, indx: usize) -> (Option<ZSVRef<'a>>, Option<ZSVRef<'a>>) {
+            if indx >= self.data.len() {
+                return (None, None);
+            }
+            let (left, right) = self.data.split_at(indx);
+            (Some(ZSVRef { data: left }), Some(ZSVRef { data: right }))
+        }
+
+        /// Splits a view into a tuple of `(a, b)`. This function is defined
+        /// as `self.split_index(self.find_string(s))`
+        pub fn split_string(&self, s: &ZSVRef<'_>) -> (Option<ZSVRef<'a>>, Option<ZSVRef<'a>>) {
+            if let Some(indx) = self.find_string(s) {
+                self.split_index(indx)
+            } else {
+                (Some(*self), None)
+            }
+        }
+
+        /// Splits a view on every instance of the seperator character `s`,
+        /// with the same behavior as `ZSV::split_all_char`, but returning
+        /// views into the original buffer.
+        pub fn split_all_char(&self, s: char) -> Vec<ZSVRef<'a>> {
+            self.data.split(s).map(|data| ZSVRef { data }).collect()
+        }
+
+        /// Copies the viewed data into a new, owned `ZSV`.
+        pub fn to_zsv(&self) -> ZSV {
+            ZSV::from(self.data)
+        }
+    }
+
+    impl<'a> From<&'a str> for ZSVRef<'a> {
+        fn from(data: &'a str) -> ZSVRef<'a> {
+            ZSVRef { data }
+        }
+    }
+
+    impl From<ZSVRef<'_>> for ZSV {
+        fn from(data: ZSVRef<'_>) -> ZSV {
+            data.to_zsv()
+        }
+    }
+
+    /// Builds the Knuth-Morris-Pratt prefix table for `pattern`, where entry
+    /// `i` is the length of the longest proper prefix of `pattern[..=i]`
+    /// that is also a suffix of it.
+    fn kmp_prefix_table<T: PartialEq>(pattern: &[T]) -> Vec<usize> {
+        let mut table: Vec<usize> = vec![0; pattern.len()];
+        let mut len: usize = 0;
+        for i in 1..pattern.len() {
+            while len > 0 && pattern[i] != pattern[len] {
+                len = table[len - 1];
+            }
+            if pattern[i] == pattern[len] {
+                len += 1;
+            }
+            table[i] = len;
+        }
+        table
+    }
+
+    /// Returns the position of the first occurrence of `needle` in
+    /// `haystack` using Knuth-Morris-Pratt, which runs in time linear in the
+    /// length of the haystack. `needle` must not be empty.
+    fn kmp_find<T: PartialEq>(haystack: impl Iterator<Item = T>, needle: &[T]) -> Option<usize> {
+        let table: Vec<usize> = kmp_prefix_table(needle);
+        let mut matched: usize = 0;
+        for (i, item) in haystack.enumerate() {
+            while matched > 0 && item != needle[matched] {
+                matched = table[matched - 1];
+            }
+            if item == needle[matched] {
+                matched += 1;
+            }
+            if matched == needle.len() {
+                return Some(i + 1 - needle.len());
+            }
+        }
+        None
+    }
+
+    mod sealed {
+        pub trait Sealed {}
+
+        impl Sealed for char {}
+        impl Sealed for &str {}
+        impl Sealed for &super::ZSV {}
+    }
+
+    /// A pattern that can be searched for with `ZSV::find`. This trait is
+    /// sealed, and is implemented for `char`, `&str`, and `&ZSV`.
+    pub trait ZSVPattern: sealed::Sealed {
+        /// Returns the byte offset of the first match of the pattern in
+        /// `haystack`, or `None` if there isn't one.
+        fn find_in(&self, haystack: &ZSV) -> Option<usize>;
+    }
+
+    impl ZSVPattern for char {
+        fn find_in(&self, haystack: &ZSV) -> Option<usize> {
+            haystack.find_char(*self)
+        }
+    }
+
+    impl ZSVPattern for &str {
+        fn find_in(&self, haystack: &ZSV) -> Option<usize> {
+            haystack.as_view().find_string(&ZSVRef::from(*self))
+        }
+    }
+
+    impl ZSVPattern for &ZSV {
+        fn find_in(&self, haystack: &ZSV) -> Option<usize> {
+            haystack.find_string(self)
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+
+    use crate::z_sv::*;
+    use std::ffi::CString;
+    use std::time::Duration;
+    #[test]
+    fn test_from_cstr() -> Result<(), ()> {
+        let j: CString = CString::new("Hello, world!").unwrap();
+        let _f: ZSV = ZSV::from(&j);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_string() -> Result<(), ()> {
+        let j: String = "The Game.".to_owned();
+        let _f: ZSV = ZSV::from(j);
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_string() -> Result<(), ()> {
+        let corpus: ZSV = ZSV::from(
+            "Lorem ipsum dolor sit amet, consectetur 
+                                    adipiscing elit. Duis vestibulum iaculis
+                                    orci ut laoreet. Nulla hendrerit sed nisl
+                                    nec tempor. Curabitur vitae tempus lorem,
+                                    quis mollis velit. Morbi pellentesque 
+                                    sodales turpis a vestibulum. Donec quis 
+                                    justo sed odio sodales bibendum in 
+                                    pellentesque erat. Pellentesque habitant 
+                                    morbi tristique senectus et netus et 
+                                    malesuada fames ac turpis egestas. 
+                                    Vestibulum tincidunt libero non vestibulum 
+                                    ultricies. Cras quis mi nunc. Donec vitae 
+                                    tortor cursus lacus aliquam aliquam nec 
+                                    gravida augue. Fusce iaculis est et est 
+                                    fermentum fringilla. Sed vestibulum arcu 
+                                    odio, at tristique nibh imperdiet vitae. 
+                                    Curabitur sed velit molestie leo mollis 
+                                    interdum sed id nunc. Sed sagittis 
+                                    scelerisque tincidunt.",
+        );
+        let text_to_find: ZSV = ZSV::from("et");
+        let loc = corpus.find_string(&text_to_find);
+        if let Some(loc) = loc {
+            if loc != 24 {
+                Err(())
+            } else {
+                Ok(())
+            }
+        } else {
+            Err(())
+        }
+    }
+
+    #[test]
+    fn test_rfind_string() -> Result<(), ()> {
+        let corpus: ZSV = ZSV::from(
+            "Lorem ipsum dolor sit amet, consectetur 
+                                    adipiscing elit. Duis vestibulum iaculis
+                                    orci ut laoreet. Nulla hendrerit sed nisl
+                                    nec tempor. Curabitur vitae tempus lorem,
+                                    quis mollis velit. Morbi pellentesque 
+                                    sodales turpis a vestibulum. Donec quis 
+                                    justo sed odio sodales bibendum in 
+                                    pellentesque erat. Pellentesque habitant 
+                                    morbi tristique senectus et netus et 
+                                    malesuada fames ac turpis egestas. 
+                                    Vestibulum tincidunt libero non vestibulum 
+                                    ultricies. Cras quis mi nunc. Donec vitae 
+                                    tortor cursus lacus aliquam aliquam nec 
+                                    gravida augue. Fusce iaculis est et est 
+                                    fermentum fringilla. Sed vestibulum arcu 
+                                    odio, at tristique nibh imperdiet vitae. 
+                                    Curabitur sed velit molestie leo mollis 
+                                    interdum sed id nunc. Sed sagittis 
+                                    scelerisque tincidunt.",
+        );
+        let text_to_find: ZSV = ZSV::from("et");
+        let loc = corpus.rfind_string(&text_to_find);
+        if let Some(loc) = loc {
+            if loc != 1180 {
+                Err(())
+            } else {
+                Ok(())
+            }
+        } else {
+            Err(())
+        }
+    }
+
+    #[test]
+    fn test_split_string() -> Result<(), ()> {
+        let data: ZSV = ZSV::from("Quick brown fox != lazy dog");
         let search: ZSV = ZSV::from("!=");
         let ret = data.split_string(&search) == data.split_index(16);
         if ret {
-            return Ok(());
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    #[test]
+    fn test_rsplitn_char() -> Result<(), ()> {
+        let path: ZSV = ZSV::from("/usr/local/bin/zsv");
+        let pieces: Vec<ZSV> = path.rsplitn_char(2, '/');
+        if pieces != vec![ZSV::from("/usr/local/bin"), ZSV::from("zsv")] {
+            return Err(());
+        }
+        let pieces: Vec<ZSV> = path.rsplitn_char(3, '/');
+        if pieces != vec![ZSV::from("/usr/local"), ZSV::from("bin"), ZSV::from("zsv")] {
+            return Err(());
+        }
+        if !path.rsplitn_char(0, '/').is_empty() {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_anagram() -> Result<(), ()> {
+        let listen: ZSV = ZSV::from("listen");
+        if !listen.is_anagram(&ZSV::from("silent")) {
+            return Err(());
+        }
+        if listen.is_anagram(&ZSV::from("listens")) || listen.is_anagram(&ZSV::from("lister")) {
+            return Err(());
+        }
+        if listen.is_anagram(&ZSV::from("Silent")) {
+            return Err(());
+        }
+        if !ZSV::from("Dormitory").is_anagram_ignore_whitespace_and_case(&ZSV::from("dirty room")) {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_middle() -> Result<(), ()> {
+        if ZSV::from("abcd").split_middle() != (ZSV::from("ab"), ZSV::from("cd")) {
+            return Err(());
+        }
+        if ZSV::from("abcde").split_middle() != (ZSV::from("abc"), ZSV::from("de")) {
+            return Err(());
+        }
+        if ZSV::from("日本語テスト").split_middle() != (ZSV::from("日本語"), ZSV::from("テスト"))
+        {
+            return Err(());
+        }
+        if ZSV::from("héllo").split_middle() != (ZSV::from("hél"), ZSV::from("lo")) {
+            return Err(());
+        }
+        if ZSV::from("").split_middle() != (ZSV::from(""), ZSV::from("")) {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_f64_fixed() -> Result<(), ()> {
+        if ZSV::from_f64_fixed(12.34567, 2) != ZSV::from("12.35") {
+            return Err(());
+        }
+        if ZSV::from_f64_fixed(0.4321, 3) != ZSV::from("0.432") {
+            return Err(());
+        }
+        if ZSV::from_f64_fixed(0.999, 2) != ZSV::from("1.00") {
+            return Err(());
+        }
+        if ZSV::from_f64_fixed(1.5, 4) != ZSV::from("1.5000") {
+            return Err(());
+        }
+        if ZSV::from_f64_fixed(-7.0, 0) != ZSV::from("-7") {
+            return Err(());
+        }
+        if ZSV::from_f64_fixed(1e21, 1) != ZSV::from("1000000000000000000000.0") {
+            return Err(());
+        }
+        if ZSV::from_f64_fixed(1e-7, 8) != ZSV::from("0.00000010") {
+            return Err(());
+        }
+        // Exact ties round to even.
+        if ZSV::from_f64_fixed(0.125, 2) != ZSV::from("0.12")
+            || ZSV::from_f64_fixed(0.375, 2) != ZSV::from("0.38")
+        {
+            return Err(());
+        }
+        if ZSV::from_f64_fixed(2.5, 0) != ZSV::from("2")
+            || ZSV::from_f64_fixed(-2.5, 0) != ZSV::from("-2")
+        {
+            return Err(());
+        }
+        if ZSV::from_f64_fixed(-0.001, 2) != ZSV::from("0.00")
+            || ZSV::from_f64_fixed(-0.0, 1) != ZSV::from("0.0")
+        {
+            return Err(());
+        }
+        if ZSV::from_f64_fixed(-0.4, 0) != ZSV::from("0")
+            || ZSV::from_f64_fixed(-0.006, 2) != ZSV::from("-0.01")
+        {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_align_offset() -> Result<(), ()> {
+        let a: ZSV = ZSV::from("config.debug.level");
+        let b: ZSV = ZSV::from("config.dry_run");
+        if a.align_offset(&b) != 8 || a.common_prefix_len(&b) != 8 {
+            return Err(());
+        }
+        if a.aligned_remainders(&b) != (ZSV::from("ebug.level"), ZSV::from("ry_run")) {
+            return Err(());
+        }
+        let c: ZSV = ZSV::from("über-alles");
+        let d: ZSV = ZSV::from("über-eins");
+        if c.align_offset(&d) != 5 {
+            return Err(());
+        }
+        if c.aligned_remainders(&d) != (ZSV::from("alles"), ZSV::from("eins")) {
+            return Err(());
+        }
+        if a.aligned_remainders(&a) != (ZSV::from(""), ZSV::from("")) {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_shingles() -> Result<(), ()> {
+        let sentence: ZSV = ZSV::from("the quick  brown\tfox");
+        let expected: Vec<ZSV> = vec![
+            ZSV::from("the quick"),
+            ZSV::from("quick brown"),
+            ZSV::from("brown fox"),
+        ];
+        if sentence.shingles(2) != expected {
+            return Err(());
+        }
+        if sentence.shingles(4) != vec![ZSV::from("the quick brown fox")] {
+            return Err(());
+        }
+        if !sentence.shingles(5).is_empty() || !sentence.shingles(0).is_empty() {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_trim_trailing_whitespace_lines() -> Result<(), ()> {
+        let source: ZSV = ZSV::from("fn main() {  \n\tfoo();\t \n\n}   ");
+        if source.trim_trailing_whitespace_lines() != ZSV::from("fn main() {\n\tfoo();\n\n}") {
+            return Err(());
+        }
+        let source: ZSV = ZSV::from("a \r\nb\t\n");
+        if source.trim_trailing_whitespace_lines() != ZSV::from("a\r\nb\n") {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_collapse_blank_lines() -> Result<(), ()> {
+        let doc: ZSV = ZSV::from("one\n\n\n\ntwo\n\nthree\n");
+        if doc.collapse_blank_lines(1) != ZSV::from("one\n\ntwo\n\nthree\n") {
+            return Err(());
+        }
+        if doc.collapse_blank_lines(2) != ZSV::from("one\n\n\ntwo\n\nthree\n") {
+            return Err(());
+        }
+        if doc.collapse_blank_lines(5) != doc {
+            return Err(());
+        }
+        if doc.collapse_blank_lines(0) != ZSV::from("one\ntwo\nthree\n") {
+            return Err(());
+        }
+        if ZSV::from("a\n \t\n\n").collapse_blank_lines(1) != ZSV::from("a\n \t\n") {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_detect_indent() -> Result<(), ()> {
+        let tabs: ZSV = ZSV::from("fn a() {\n\tif b {\n\t\tc();\n\t}\n}\n");
+        if tabs.detect_indent() != Some(IndentStyle::Tabs) {
+            return Err(());
+        }
+        let two: ZSV = ZSV::from("a:\n  b:\n    c: 1\n\n  d: 2\n");
+        if two.detect_indent() != Some(IndentStyle::Spaces(2)) {
+            return Err(());
+        }
+        let four: ZSV = ZSV::from("def f():\n    if x:\n        return 1\n    return 2\n");
+        if four.detect_indent() != Some(IndentStyle::Spaces(4)) {
+            return Err(());
+        }
+        let mixed: ZSV = ZSV::from("a\n\tb\n    c\n    d\n");
+        if mixed.detect_indent() != Some(IndentStyle::Spaces(4)) {
+            return Err(());
+        }
+        if ZSV::from("flat\ntext\n").detect_indent().is_some() {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_top_level_groups() -> Result<(), ()> {
+        let expr: ZSV = ZSV::from("f(a, (b + c)) * (d)");
+        if expr.top_level_groups('(', ')') != vec![ZSV::from("a, (b + c)"), ZSV::from("d")] {
+            return Err(());
+        }
+        let seq: ZSV = ZSV::from("[1][2][[3]]");
+        if seq.top_level_groups('[', ']') != vec![ZSV::from("1"), ZSV::from("2"), ZSV::from("[3]")]
+        {
+            return Err(());
+        }
+        let unbalanced: ZSV = ZSV::from(") (x) (y");
+        if unbalanced.top_level_groups('(', ')') != vec![ZSV::from("x")] {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_all_char_escaped() -> Result<(), ()> {
+        let row: ZSV = ZSV::from(r"a\,b,c,d\\,e\n");
+        let expected: Vec<ZSV> = vec![
+            ZSV::from("a,b"),
+            ZSV::from("c"),
+            ZSV::from(r"d\"),
+            ZSV::from(r"e\n"),
+        ];
+        if row.split_all_char_escaped(',', '\\') != expected {
+            return Err(());
+        }
+        let trailing: ZSV = ZSV::from(r"x,y\");
+        if trailing.split_all_char_escaped(',', '\\') != vec![ZSV::from("x"), ZSV::from(r"y\")] {
+            return Err(());
+        }
+        if ZSV::from("").split_all_char_escaped(',', '\\') != vec![ZSV::from("")] {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_byte_windows() -> Result<(), ()> {
+        let buf: ZSV = ZSV::from("abcd");
+        let expected: Vec<&[u8]> = vec![b"abc", b"bcd"];
+        if buf.byte_windows(3) != expected {
+            return Err(());
+        }
+        if buf.byte_windows(4) != vec![b"abcd"] {
+            return Err(());
+        }
+        if !buf.byte_windows(5).is_empty() || !buf.byte_windows(0).is_empty() {
+            return Err(());
+        }
+        if ZSV::from("é").byte_windows(1) != vec![&[0xC3u8][..], &[0xA9u8][..]] {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_duration() -> Result<(), ()> {
+        let cases: [(&str, Duration); 6] = [
+            ("500ms", Duration::from_millis(500)),
+            ("  2s ", Duration::from_secs(2)),
+            ("5m", Duration::from_secs(300)),
+            ("1h", Duration::from_secs(3600)),
+            ("250us", Duration::from_micros(250)),
+            ("10ns", Duration::from_nanos(10)),
+        ];
+        for (input, expected) in cases {
+            if ZSV::from(input).parse_duration() != Ok(expected) {
+                return Err(());
+            }
+        }
+        if ZSV::from("3d").parse_duration() != Err(ParseError::InvalidUnit(ZSV::from("d"))) {
+            return Err(());
+        }
+        if ZSV::from("12").parse_duration() != Err(ParseError::InvalidUnit(ZSV::from(""))) {
+            return Err(());
+        }
+        if ZSV::from("ms").parse_duration() != Err(ParseError::InvalidNumber(ZSV::from(""))) {
+            return Err(());
+        }
+        if ZSV::from("   ").parse_duration() != Err(ParseError::Empty) {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_soundex() -> Result<(), ()> {
+        let cases: [(&str, &str); 8] = [
+            ("Robert", "R163"),
+            ("Rupert", "R163"),
+            ("Rubin", "R150"),
+            ("Ashcraft", "A261"),
+            ("Tymczak", "T522"),
+            ("Pfister", "P236"),
+            ("Honeyman", "H555"),
+            ("  o'Hara!", "O600"),
+        ];
+        for (name, expected) in cases {
+            if ZSV::from(name).soundex() != ZSV::from(expected) {
+                return Err(());
+            }
+        }
+        if ZSV::from("1234 -").soundex() != ZSV::from("") {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_strip_shebang() -> Result<(), ()> {
+        let script: ZSV = ZSV::from("#!/usr/bin/env python3\nprint('hi')\n");
+        let expected: (Option<ZSV>, ZSV) = (
+            Some(ZSV::from("#!/usr/bin/env python3")),
+            ZSV::from("print('hi')\n"),
+        );
+        if script.strip_shebang() != expected {
+            return Err(());
+        }
+        let plain: ZSV = ZSV::from("echo hi\n#!not a shebang\n");
+        if plain.strip_shebang() != (None, plain.clone()) {
+            return Err(());
+        }
+        let only: ZSV = ZSV::from("#!/bin/sh");
+        if only.strip_shebang() != (Some(ZSV::from("#!/bin/sh")), ZSV::from("")) {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_line_map() -> Result<(), ()> {
+        let text: ZSV = ZSV::from("let a = 1;\nlet bé = 2;\n\nend");
+        let map: LineMap = text.build_line_map();
+        if map.line_count() != 4 {
+            return Err(());
+        }
+        let positions: [(usize, (usize, usize)); 6] = [
+            (0, (0, 0)),
+            (10, (0, 10)),
+            (11, (1, 0)),
+            (17, (1, 6)),
+            (24, (2, 0)),
+            (28, (3, 3)),
+        ];
+        for (byte, position) in positions {
+            if map.byte_to_position(byte) != Some(position) {
+                return Err(());
+            }
+            if map.position_to_byte(position.0, position.1) != Some(byte) {
+                return Err(());
+            }
+        }
+        if map.byte_to_position(29).is_some() {
+            return Err(());
+        }
+        if map.position_to_byte(0, 11).is_some() || map.position_to_byte(4, 0).is_some() {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_columns() -> Result<(), ()> {
+        // NAME      SIZE  OWNER
+        let starts: [usize; 3] = [0, 10, 16];
+        let row: ZSV = ZSV::from("café.txt  1024  zane\n");
+        let expected: Vec<ZSV> = vec![
+            ZSV::from("café.txt  "),
+            ZSV::from("1024  "),
+            ZSV::from("zane"),
+        ];
+        if row.split_columns(&starts) != expected {
+            return Err(());
+        }
+        let short: ZSV = ZSV::from("a.out     7");
+        let expected: Vec<ZSV> = vec![ZSV::from("a.out     "), ZSV::from("7"), ZSV::from("")];
+        if short.split_columns(&starts) != expected {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_dice_coefficient() -> Result<(), ()> {
+        let night: ZSV = ZSV::from("night");
+        if night.dice_coefficient(&night) != 1.0 {
+            return Err(());
+        }
+        // {ni, ig, gh, ht} vs {na, ac, ch, ht} share only "ht".
+        if night.dice_coefficient(&ZSV::from("nacht")) != 0.25 {
+            return Err(());
+        }
+        if night.dice_coefficient(&ZSV::from("xyz")) != 0.0 {
+            return Err(());
+        }
+        if ZSV::from("a").dice_coefficient(&ZSV::from("a")) != 1.0 {
+            return Err(());
+        }
+        if ZSV::from("a").dice_coefficient(&night) != 0.0 {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_longest_common_substring() -> Result<(), ()> {
+        let a: ZSV = ZSV::from("xabcdey");
+        let b: ZSV = ZSV::from("zzbcdezz");
+        if a.longest_common_substring(&b) != ZSV::from("bcde") {
+            return Err(());
+        }
+        if ZSV::from("日本語テスト").longest_common_substring(&ZSV::from("英語テキスト"))
+            != ZSV::from("語テ")
+        {
+            return Err(());
+        }
+        if ZSV::from("abab").longest_common_substring(&ZSV::from("ba")) != ZSV::from("ba") {
+            return Err(());
+        }
+        if ZSV::from("abc").longest_common_substring(&ZSV::from("xyz")) != ZSV::from("") {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_highlight() -> Result<(), ()> {
+        let line: ZSV = ZSV::from("error: bad; another error here");
+        let open: ZSV = ZSV::from("\x1b[31m");
+        let close: ZSV = ZSV::from("\x1b[0m");
+        let expected: ZSV =
+            ZSV::from("\x1b[31merror\x1b[0m: bad; another \x1b[31merror\x1b[0m here");
+        if line.highlight(&ZSV::from("error"), &open, &close) != expected {
+            return Err(());
+        }
+        if ZSV::from("aaa").highlight(&ZSV::from("aa"), &ZSV::from("["), &ZSV::from("]"))
+            != ZSV::from("[aa]a")
+        {
+            return Err(());
+        }
+        if line.highlight(&ZSV::from("warning"), &open, &close) != line {
+            return Err(());
+        }
+        if line.highlight(&ZSV::from(""), &open, &close) != line {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_logical_lines() -> Result<(), ()> {
+        let make: ZSV = ZSV::from("CFLAGS = -O2 \\\n\t-Wall \\\r\n\t-g\nall: main\n");
+        let expected: Vec<ZSV> = vec![
+            ZSV::from("CFLAGS = -O2 \t-Wall \t-g"),
+            ZSV::from("all: main"),
+        ];
+        if make.logical_lines() != expected {
+            return Err(());
+        }
+        let plain: ZSV = ZSV::from("one\ntwo\nthree");
+        if plain.logical_lines() != vec![ZSV::from("one"), ZSV::from("two"), ZSV::from("three")] {
+            return Err(());
+        }
+        if ZSV::from("dangling \\").logical_lines() != vec![ZSV::from("dangling ")] {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_bucket() -> Result<(), ()> {
+        if ZSV::from("").stable_hash() != 0xcbf29ce484222325
+            || ZSV::from("a").stable_hash() != 0xaf63dc4c8601ec8c
+        {
+            return Err(());
+        }
+        let keys: [&str; 4] = ["user:1", "user:2", "session:abc", "日本"];
+        for key in keys {
+            let first: usize = ZSV::from(key).bucket(16);
+            if first >= 16 || ZSV::from(key).bucket(16) != first {
+                return Err(());
+            }
+        }
+        if ZSV::from("anything").bucket(1) != 0 {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bucket_zero() {
+        ZSV::from("key").bucket(0);
+    }
+
+    #[test]
+    fn test_has_mixed_scripts() -> Result<(), ()> {
+        if ZSV::from("paypal.com").has_mixed_scripts() {
+            return Err(());
+        }
+        if ZSV::from("привет, мир 2024").has_mixed_scripts() {
+            return Err(());
+        }
+        // The first 'а' here is CYRILLIC SMALL LETTER A.
+        if !ZSV::from("p\u{0430}ypal.com").has_mixed_scripts() {
+            return Err(());
+        }
+        if ZSV::from("日本語のテスト").has_mixed_scripts() {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_path_components() -> Result<(), ()> {
+        let usr_bin: Vec<ZSV> = vec![ZSV::from("usr"), ZSV::from("bin")];
+        if ZSV::from("/usr/bin").path_components() != usr_bin {
+            return Err(());
+        }
+        if ZSV::from("usr//bin/").path_components() != usr_bin {
+            return Err(());
+        }
+        let relative: Vec<ZSV> = vec![ZSV::from("."), ZSV::from("src"), ZSV::from("lib.rs")];
+        if ZSV::from("./src/lib.rs").path_components() != relative {
+            return Err(());
+        }
+        if ZSV::from("/").path_components() != Vec::<ZSV>::new() {
+            return Err(());
+        }
+        let windows: Vec<ZSV> = vec![ZSV::from("C:"), ZSV::from("Users"), ZSV::from("zane")];
+        if ZSV::from("C:\\Users/zane\\").path_components_with_backslash() != windows {
+            return Err(());
+        }
+        if ZSV::from("a\\b").path_components() != vec![ZSV::from("a\\b")] {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_table() -> Result<(), ()> {
+        let row = |cells: &[&str]| -> Vec<ZSV> { cells.iter().map(|&c| ZSV::from(c)).collect() };
+        let rows: Vec<Vec<ZSV>> = vec![
+            row(&["name", "size", "owner"]),
+            row(&["café.txt", "1024", "zane"]),
+            row(&["a", "7"]),
+        ];
+        let expected: ZSV = ZSV::from(
+            "name     | size | owner\n\
+             café.txt | 1024 | zane\n\
+             a        | 7\n",
+        );
+        if ZSV::render_table(&rows) != expected {
+            return Err(());
+        }
+        if ZSV::render_table(&[]) != ZSV::from("") {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_balanced() -> Result<(), ()> {
+        if !ZSV::from("fn f(a: [u8; 2]) { g({x}); }").is_balanced() {
+            return Err(());
+        }
+        if ZSV::from("(]").is_balanced()
+            || ZSV::from("((x)").is_balanced()
+            || ZSV::from("x)").is_balanced()
+        {
+            return Err(());
+        }
+        if !ZSV::from(r#"print(")", '[', "\"}")"#).is_balanced() {
+            return Err(());
+        }
+        if ZSV::from(r#"f("(")"#).is_balanced() != ZSV::from("f()").is_balanced() {
+            return Err(());
+        }
+        if ZSV::from("f('unterminated)").is_balanced() {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_utf16_len() -> Result<(), ()> {
+        let text: ZSV = ZSV::from("a😀b");
+        if text.utf16_len() != 4 || ZSV::from("héllo").utf16_len() != 5 {
+            return Err(());
+        }
+        if text.truncate_utf16(2) != ZSV::from("a") {
+            return Err(());
+        }
+        if text.truncate_utf16(3) != ZSV::from("a😀") {
+            return Err(());
+        }
+        if text.truncate_utf16(10) != text || text.truncate_utf16(0) != ZSV::from("") {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_shannon_entropy() -> Result<(), ()> {
+        if ZSV::from("").shannon_entropy() != 0.0 || ZSV::from("aaaaaaaa").shannon_entropy() != 0.0
+        {
+            return Err(());
+        }
+        if (ZSV::from("abcd").shannon_entropy() - 2.0).abs() > 1e-9 {
+            return Err(());
+        }
+        let repetitive: f64 = ZSV::from("abababababab").shannon_entropy();
+        let varied: f64 = ZSV::from("q8Zr!x2Lp0@v").shannon_entropy();
+        if repetitive >= varied {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_sentences_preserving() -> Result<(), ()> {
+        let prose: ZSV = ZSV::from("  Pi is 3.14.  Is it?! \"Yes.\"\nDone");
+        let expected: Vec<ZSV> = vec![
+            ZSV::from("  Pi is 3.14.  "),
+            ZSV::from("Is it?! "),
+            ZSV::from("\"Yes.\"\n"),
+            ZSV::from("Done"),
+        ];
+        let sentences: Vec<ZSV> = prose.split_sentences_preserving();
+        if sentences != expected {
+            return Err(());
+        }
+        let rebuilt: String = sentences.iter().map(|s| s.data.as_str()).collect();
+        if rebuilt != prose.data {
+            return Err(());
+        }
+        if !ZSV::from("").split_sentences_preserving().is_empty() {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_ini_line() -> Result<(), ()> {
+        if ZSV::from("  [ server ]  ").parse_ini_line() != IniLine::Section(ZSV::from("server")) {
+            return Err(());
+        }
+        let pair: IniLine = IniLine::KeyValue(ZSV::from("url"), ZSV::from("http://x/?a=b"));
+        if ZSV::from("url = http://x/?a=b").parse_ini_line() != pair {
+            return Err(());
+        }
+        if ZSV::from("; a comment").parse_ini_line() != IniLine::Comment(ZSV::from("a comment")) {
+            return Err(());
+        }
+        if ZSV::from("#another").parse_ini_line() != IniLine::Comment(ZSV::from("another")) {
+            return Err(());
+        }
+        if ZSV::from(" \t").parse_ini_line() != IniLine::Blank {
+            return Err(());
+        }
+        if ZSV::from("flag").parse_ini_line() != IniLine::KeyValue(ZSV::from("flag"), ZSV::from(""))
+        {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_csv_fields() -> Result<(), ()> {
+        let row: ZSV = ZSV::from(r#"plain,"with, comma","say ""hi""",,"""""#);
+        let expected: Vec<ZSV> = vec![
+            ZSV::from("plain"),
+            ZSV::from("with, comma"),
+            ZSV::from("say \"hi\""),
+            ZSV::from(""),
+            ZSV::from("\""),
+        ];
+        if row.csv_fields(',').collect::<Vec<ZSV>>() != expected {
+            return Err(());
+        }
+        let row: ZSV = ZSV::from("a;\"b;c\";");
+        let mut fields: CsvFieldIter = row.csv_fields(';');
+        if fields.next() != Some(ZSV::from("a")) || fields.next() != Some(ZSV::from("b;c")) {
+            return Err(());
+        }
+        if fields.next() != Some(ZSV::from("")) || fields.next().is_some() {
+            return Err(());
+        }
+        if ZSV::from("").csv_fields(',').collect::<Vec<ZSV>>() != vec![ZSV::from("")] {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "unicode-norm")]
+    #[test]
+    fn test_normalize() -> Result<(), ()> {
+        let composed: ZSV = ZSV::from("caf\u{e9}");
+        let decomposed: ZSV = ZSV::from("cafe\u{301}");
+        if composed == decomposed {
+            return Err(());
+        }
+        if composed.normalize(NormalizationForm::Nfc)
+            != decomposed.normalize(NormalizationForm::Nfc)
+        {
+            return Err(());
+        }
+        if decomposed.normalize(NormalizationForm::Nfc) != composed {
+            return Err(());
+        }
+        if composed.normalize(NormalizationForm::Nfd) != decomposed {
+            return Err(());
+        }
+        if ZSV::from("\u{fb01}").normalize(NormalizationForm::Nfkc) != ZSV::from("fi") {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_char_with_index() -> Result<(), ()> {
+        let pair: ZSV = ZSV::from("key=value=more");
+        if pair.split_char_with_index('=') != Some((ZSV::from("key"), 3, ZSV::from("value=more"))) {
+            return Err(());
+        }
+        let wide: ZSV = ZSV::from("日本→語");
+        if wide.split_char_with_index('→') != Some((ZSV::from("日本"), 6, ZSV::from("語"))) {
+            return Err(());
+        }
+        if pair.split_char_with_index(':').is_some() {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_supplementary_char_count() -> Result<(), ()> {
+        if ZSV::from("hi 😀👍🏽!").supplementary_char_count() != 3 {
+            return Err(());
+        }
+        if ZSV::from("日本語 café").supplementary_char_count() != 0 {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "width")]
+    #[test]
+    fn test_truncate_display_width() -> Result<(), ()> {
+        let text: ZSV = ZSV::from("ab日本語");
+        if text.display_width() != 8 {
+            return Err(());
+        }
+        // "ab日本" is 6 columns, so the next wide character would overflow
+        // a 7-column limit by one.
+        if text.truncate_display_width(7) != ZSV::from("ab日本") {
+            return Err(());
+        }
+        if text.truncate_display_width(8) != text
+            || text.truncate_display_width(1) != ZSV::from("a")
+        {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_html_escape() -> Result<(), ()> {
+        let raw: ZSV = ZSV::from(r#"<a href="x">Tom & Jerry's</a>"#);
+        let escaped: ZSV = raw.html_escape();
+        if escaped != ZSV::from("&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;") {
+            return Err(());
+        }
+        if escaped.html_unescape() != raw {
+            return Err(());
+        }
+        if ZSV::from("&#x41;&#66;&apos;&nbsp;").html_unescape() != ZSV::from("AB'\u{a0}") {
+            return Err(());
+        }
+        if ZSV::from("AT&T &bogus; &#xZZ; &").html_unescape() != ZSV::from("AT&T &bogus; &#xZZ; &")
+        {
+            return Err(());
+        }
+        if ZSV::from("&#+65; &#x+41; &#-65;").html_unescape() != ZSV::from("&#+65; &#x+41; &#-65;")
+        {
+            return Err(());
+        }
+        if ZSV::from("&#x10FFFF;").html_unescape() != ZSV::from("\u{10ffff}") {
+            return Err(());
+        }
+        // The `;` is past the lookahead window, so this isn't an entity.
+        if ZSV::from("&#0000000065;").html_unescape() != ZSV::from("&#0000000065;") {
+            return Err(());
+        }
+        // Many `&`s with no `;` must stay linear rather than rescanning.
+        let ampersands: ZSV = ZSV::from("&".repeat(200_000));
+        if ampersands.html_unescape() != ampersands {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_on_first_of() -> Result<(), ()> {
+        let ops: Vec<ZSV> = vec![ZSV::from("=="), ZSV::from("!="), ZSV::from("=")];
+        let expr: ZSV = ZSV::from("a != b == c");
+        if expr.split_on_first_of(&ops) != Some((ZSV::from("a "), 1, ZSV::from(" b == c"))) {
+            return Err(());
+        }
+        // Both "==" and "=" match at the same position, so the first listed wins.
+        let expr: ZSV = ZSV::from("x == y");
+        if expr.split_on_first_of(&ops) != Some((ZSV::from("x "), 0, ZSV::from(" y"))) {
+            return Err(());
+        }
+        if ZSV::from("no ops").split_on_first_of(&ops).is_some() {
+            return Err(());
+        }
+        if ZSV::from("abc")
+            .split_on_first_of(&[ZSV::from("")])
+            .is_some()
+        {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_unwrap_delimiters() -> Result<(), ()> {
+        if ZSV::from("[abc]").unwrap_delimiters('[', ']') != Some(ZSV::from("abc")) {
+            return Err(());
+        }
+        if ZSV::from("\"quoted\"").unwrap_delimiters('"', '"') != Some(ZSV::from("quoted")) {
+            return Err(());
+        }
+        if ZSV::from("[]").unwrap_delimiters('[', ']') != Some(ZSV::from("")) {
+            return Err(());
+        }
+        if ZSV::from("[abc)").unwrap_delimiters('[', ']').is_some()
+            || ZSV::from("abc]").unwrap_delimiters('[', ']').is_some()
+        {
+            return Err(());
+        }
+        if ZSV::from("\"").unwrap_delimiters('"', '"').is_some()
+            || ZSV::from("").unwrap_delimiters('[', ']').is_some()
+        {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_longest_run() -> Result<(), ()> {
+        let fence: ZSV = ZSV::from("`a` ``b`` ```c``` `");
+        if fence.longest_run('`') != 3 {
+            return Err(());
+        }
+        if ZSV::from("éé-ééé-é").longest_run('é') != 3 || fence.longest_run('~') != 0 {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_chars() -> Result<(), ()> {
+        let text: ZSV = ZSV::from("日本語 café");
+        let chars: Vec<char> = text.to_chars();
+        if chars.len() != 8 || chars[2] != '語' || chars[7] != 'é' {
+            return Err(());
+        }
+        if ZSV::from_chars(&chars) != text || ZSV::from_chars(&chars[4..]) != ZSV::from("café") {
+            return Err(());
+        }
+        if !ZSV::from("").to_chars().is_empty() || ZSV::from_chars(&[]) != ZSV::from("") {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_semver() -> Result<(), ()> {
+        if ZSV::from("1.2.3").parse_semver() != Some((1, 2, 3, None)) {
+            return Err(());
+        }
+        if ZSV::from("10.0.42-rc1").parse_semver() != Some((10, 0, 42, Some(ZSV::from("rc1")))) {
+            return Err(());
+        }
+        if ZSV::from("1.2.3-beta-2").parse_semver() != Some((1, 2, 3, Some(ZSV::from("beta-2")))) {
+            return Err(());
+        }
+        let invalid: [&str; 7] = [
+            "1.2", "1.2.3.4", "1.x.3", "1.2.3-", "v1.2.3", "1..3", "1.2.+3",
+        ];
+        for version in invalid {
+            if ZSV::from(version).parse_semver().is_some() {
+                return Err(());
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_nth_newline_byte() -> Result<(), ()> {
+        let log: ZSV = ZSV::from("first\nsecond é\n\nlast");
+        if log.nth_newline_byte(1) != Some(5) || log.nth_newline_byte(2) != Some(15) {
+            return Err(());
+        }
+        if log.nth_newline_byte(3) != Some(16) || log.nth_newline_byte(4).is_some() {
+            return Err(());
+        }
+        if log.nth_newline_byte(0).is_some() {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_lex_simple() -> Result<(), ()> {
+        let space = || Token::Whitespace(ZSV::from(" "));
+        let expected: Vec<Token> = vec![
+            Token::Ident(ZSV::from("foo")),
+            space(),
+            Token::Symbol('='),
+            space(),
+            Token::Ident(ZSV::from("bar")),
+            space(),
+            Token::Symbol('+'),
+            space(),
+            Token::Number(ZSV::from("42")),
+        ];
+        if ZSV::from("foo = bar + 42").lex_simple() != expected {
+            return Err(());
+        }
+        let expected: Vec<Token> = vec![
+            Token::Ident(ZSV::from("_x1")),
+            Token::Symbol('('),
+            Token::Number(ZSV::from("3.5")),
+            Token::Symbol(','),
+            Token::Whitespace(ZSV::from("\t\n")),
+            Token::Number(ZSV::from("7")),
+            Token::Symbol('.'),
+            Token::Symbol(')'),
+        ];
+        if ZSV::from("_x1(3.5,\t\n7.)").lex_simple() != expected {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_valid_identifier() -> Result<(), ()> {
+        for valid in ["x", "_", "snake_case_2", "_Private"] {
+            if !ZSV::from(valid).is_valid_identifier() {
+                return Err(());
+            }
+        }
+        for invalid in ["", "1st", "kebab-case", "has space", "café"] {
+            if ZSV::from(invalid).is_valid_identifier() {
+                return Err(());
+            }
+        }
+        if !ZSV::from("café").is_valid_unicode_identifier()
+            || !ZSV::from("変数1").is_valid_unicode_identifier()
+        {
+            return Err(());
+        }
+        if ZSV::from("1変数").is_valid_unicode_identifier()
+            || ZSV::from("").is_valid_unicode_identifier()
+        {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_infer_columns() -> Result<(), ()> {
+        let header: ZSV = ZSV::from("  PID  USER      COMMAND\n");
+        let starts: Vec<usize> = ZSV::infer_columns(&header);
+        if starts != vec![2, 7, 17] {
+            return Err(());
+        }
+        let row: ZSV = ZSV::from(" 1234  zane      cargo test");
+        let expected: Vec<ZSV> = vec![
+            ZSV::from("1234"),
+            ZSV::from("zane"),
+            ZSV::from("cargo test"),
+        ];
+        if row.split_by_inferred(&starts) != expected {
+            return Err(());
+        }
+        let row: ZSV = ZSV::from("    1  root      init");
+        if row.split_by_inferred(&starts)
+            != vec![ZSV::from("1"), ZSV::from("root"), ZSV::from("init")]
+        {
+            return Err(());
+        }
+        if !ZSV::infer_columns(&ZSV::from("   ")).is_empty() {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_edit_script() -> Result<(), ()> {
+        fn apply(source: &ZSV, script: &[EditOp]) -> Option<ZSV> {
+            let mut chars = source.data.chars();
+            let mut out: String = String::new();
+            for op in script {
+                match *op {
+                    EditOp::Keep(c) => {
+                        if chars.next() != Some(c) {
+                            return None;
+                        }
+                        out.push(c);
+                    }
+                    EditOp::Delete(c) => {
+                        if chars.next() != Some(c) {
+                            return None;
+                        }
+                    }
+                    EditOp::Insert(c) => out.push(c),
+                }
+            }
+            if chars.next().is_some() {
+                return None;
+            }
+            Some(ZSV::from(out))
+        }
+        let pairs: [(&str, &str); 5] = [
+            ("kitten", "sitting"),
+            ("", "abc"),
+            ("abc", ""),
+            ("flaw", "lawn"),
+            ("日本語", "日本人語"),
+        ];
+        for (from, to) in pairs {
+            let (from, to): (ZSV, ZSV) = (ZSV::from(from), ZSV::from(to));
+            if apply(&from, &from.edit_script(&to)) != Some(to) {
+                return Err(());
+            }
+        }
+        let script: Vec<EditOp> = ZSV::from("cat").edit_script(&ZSV::from("cut"));
+        let expected: Vec<EditOp> = vec![
+            EditOp::Keep('c'),
+            EditOp::Delete('a'),
+            EditOp::Insert('u'),
+            EditOp::Keep('t'),
+        ];
+        if script != expected {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_all_char_in_range() -> Result<(), ()> {
+        let line: ZSV = ZSV::from("a,b,c,d,e");
+        let expected: Vec<ZSV> = vec![ZSV::from("a,b"), ZSV::from("c"), ZSV::from("d,e")];
+        if line.split_all_char_in_range(',', 3..6) != expected {
+            return Err(());
+        }
+        if line.split_all_char_in_range(',', 0..1) != vec![line.clone()] {
+            return Err(());
+        }
+        let wide: ZSV = ZSV::from("é|é|é");
+        if wide.split_all_char_in_range('|', 2..10) != vec![ZSV::from("é|é"), ZSV::from("é")] {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_ranges() -> Result<(), ()> {
+        if ZSV::from("1-3,5, 7 - 9").parse_ranges() != Ok(vec![1..=3, 5..=5, 7..=9]) {
+            return Err(());
+        }
+        if ZSV::from("5-3").parse_ranges() != Err(ParseError::ReversedRange(ZSV::from("5-3"))) {
+            return Err(());
+        }
+        if ZSV::from("1,x,3").parse_ranges() != Err(ParseError::InvalidNumber(ZSV::from("x"))) {
+            return Err(());
+        }
+        if ZSV::from("1,,3").parse_ranges() != Err(ParseError::InvalidNumber(ZSV::from(""))) {
+            return Err(());
+        }
+        if ZSV::from("2-").parse_ranges().is_ok()
+            || ZSV::from(" ").parse_ranges() != Err(ParseError::Empty)
+        {
+            return Err(());
+        }
+        if ZSV::from("").parse_ranges() != Err(ParseError::Empty) {
+            return Err(());
+        }
+        if ZSV::from("1-3,").parse_ranges() != Err(ParseError::InvalidNumber(ZSV::from(""))) {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_common_prefix() -> Result<(), ()> {
+        let items: Vec<ZSV> = vec![
+            ZSV::from("git-commit"),
+            ZSV::from("git-config"),
+            ZSV::from("git-clone"),
+        ];
+        if ZSV::common_prefix(&items) != ZSV::from("git-c") {
+            return Err(());
+        }
+        let items: Vec<ZSV> = vec![ZSV::from("über"), ZSV::from("übel")];
+        if ZSV::common_prefix(&items) != ZSV::from("übe") {
+            return Err(());
+        }
+        let items: Vec<ZSV> = vec![ZSV::from("alpha"), ZSV::from("beta")];
+        if ZSV::common_prefix(&items) != ZSV::from("") || ZSV::common_prefix(&[]) != ZSV::from("") {
+            return Err(());
+        }
+        if ZSV::common_prefix(&[ZSV::from("solo")]) != ZSV::from("solo") {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_nested() -> Result<(), ()> {
+        let text = |s: &str| NestedNode::Text(ZSV::from(s));
+        let tree: NestedNode = NestedNode::Group(vec![
+            text("a"),
+            NestedNode::Group(vec![
+                text("b"),
+                NestedNode::Group(vec![text("c")]),
+                text("d"),
+            ]),
+            text("e"),
+            NestedNode::Group(vec![]),
+        ]);
+        if ZSV::from("a(b(c)d)e()").parse_nested('(', ')') != Ok(tree) {
+            return Err(());
+        }
+        if ZSV::from("flat text").parse_nested('(', ')')
+            != Ok(NestedNode::Group(vec![text("flat text")]))
+        {
+            return Err(());
+        }
+        if ZSV::from("é[x]]").parse_nested('[', ']') != Err(ParseError::UnmatchedClose(5)) {
+            return Err(());
+        }
+        if ZSV::from("[a[b]").parse_nested('[', ']') != Err(ParseError::UnclosedOpen(0)) {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_strip_line_comments() -> Result<(), ()> {
+        let config: ZSV = ZSV::from(
+            "# header\nname = \"zane # not a comment\" # a comment\npath = /tmp\nq = \"a \\\" # b\"#c\r\n",
+        );
+        let expected: ZSV =
+            ZSV::from("\nname = \"zane # not a comment\" \npath = /tmp\nq = \"a \\\" # b\"\r\n");
+        if config.strip_line_comments('#') != expected {
+            return Err(());
+        }
+        let unterminated: ZSV = ZSV::from("a = \"open ; x\nb = 2 ; y");
+        if unterminated.strip_line_comments(';') != ZSV::from("a = \"open ; x\nb = 2 ") {
+            return Err(());
+        }
+        if ZSV::from("c = '#' # note").strip_line_comments('#') != ZSV::from("c = '") {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_string_at_end() -> Result<(), ()> {
+        if ZSV::from("abcd").find_string(&ZSV::from("cd")) != Some(2) {
+            return Err(());
+        }
+        if ZSV::from("abc").find_string(&ZSV::from("bc")) != Some(1) {
+            return Err(());
+        }
+        if ZSV::from("abc").find_string(&ZSV::from("abc")) != Some(0) {
+            return Err(());
+        }
+        if ZSV::from("abc").find_string(&ZSV::from("c")) != Some(2) {
+            return Err(());
+        }
+        if ZSV::from("abc").find_string(&ZSV::from("cx")).is_some() {
+            return Err(());
+        }
+        if ZSV::from("ééé").find_string(&ZSV::from("abcd")).is_some() {
+            return Err(());
+        }
+        if ZSV::from("ééé").rfind_string(&ZSV::from("abcd")).is_some() {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_seeded_chars() -> Result<(), ()> {
+        let alphabet: [char; 4] = ['a', 'b', 'é', '日'];
+        let first: ZSV = ZSV::from_seeded_chars(42, &alphabet, 64);
+        if first != ZSV::from_seeded_chars(42, &alphabet, 64) {
+            return Err(());
+        }
+        if first.data.chars().count() != 64 || !first.data.chars().all(|c| alphabet.contains(&c)) {
+            return Err(());
+        }
+        if first == ZSV::from_seeded_chars(43, &alphabet, 64) {
+            return Err(());
+        }
+        if ZSV::from_seeded_chars(42, &[], 8) != ZSV::from("") {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_multibyte_indices() -> Result<(), ()> {
+        let corpus: ZSV = ZSV::from("日本語テスト");
+        if corpus.find_char('語') != Some(6) || corpus.find_char('ト') != Some(15) {
+            return Err(());
+        }
+        if corpus.find_string(&ZSV::from("テスト")) != Some(9)
+            || corpus.rfind_string(&ZSV::from("本")) != Some(3)
+        {
+            return Err(());
+        }
+        if corpus.rfind_string(&ZSV::from("")) != Some(15) {
+            return Err(());
+        }
+        if corpus.split_char('テ') != (Some(ZSV::from("日本語")), Some(ZSV::from("テスト")))
+        {
+            return Err(());
+        }
+        let indx: usize = corpus.find_string(&ZSV::from("語")).ok_or(())?;
+        if let (Some(left), Some(right)) = corpus.split_index(indx) {
+            if left.data + &right.data != corpus.data || right.find_char('語') != Some(0) {
+                return Err(());
+            }
         } else {
             return Err(());
         }
+        let mixed: ZSV = ZSV::from("héllo wörld");
+        if mixed.find_char('l') != Some(3)
+            || mixed.split_string(&ZSV::from("wö")) != mixed.split_index(7)
+        {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_strip_length_prefix() -> Result<(), ()> {
+        let frame: ZSV = ZSV::from("0005hello0003bye");
+        if frame.strip_length_prefix(4) != Some((5, ZSV::from("hello0003bye"))) {
+            return Err(());
+        }
+        if ZSV::from("12").strip_length_prefix(2) != Some((12, ZSV::from(""))) {
+            return Err(());
+        }
+        if ZSV::from("00x5hello").strip_length_prefix(4).is_some()
+            || ZSV::from("+005hello").strip_length_prefix(4).is_some()
+        {
+            return Err(());
+        }
+        if ZSV::from("12").strip_length_prefix(4).is_some()
+            || ZSV::from("1é").strip_length_prefix(2).is_some()
+        {
+            return Err(());
+        }
+        if frame.strip_length_prefix(0).is_some() {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_rfind_char() -> Result<(), ()> {
+        let data: ZSV = ZSV::from("abcb");
+        if data.rfind_char('b') != Some(3) || data.rfind_char('a') != Some(0) {
+            return Err(());
+        }
+        if data.rfind_char('z').is_some() || ZSV::from("").rfind_char('a').is_some() {
+            return Err(());
+        }
+        let wide: ZSV = ZSV::from("é日é日x");
+        if wide.rfind_char('日') != wide.data.rfind('日') || wide.rfind_char('é') != Some(5) {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_all_char() -> Result<(), ()> {
+        let expected: Vec<ZSV> = vec![
+            ZSV::from("a"),
+            ZSV::from("b"),
+            ZSV::from(""),
+            ZSV::from("c"),
+        ];
+        if ZSV::from("a,b,,c").split_all_char(',') != expected {
+            return Err(());
+        }
+        if ZSV::from("a,b,").split_all_char(',')
+            != vec![ZSV::from("a"), ZSV::from("b"), ZSV::from("")]
+        {
+            return Err(());
+        }
+        if ZSV::from("").split_all_char(',') != vec![ZSV::from("")] {
+            return Err(());
+        }
+        if ZSV::from("日→本→語").split_all_char('→')
+            != vec![ZSV::from("日"), ZSV::from("本"), ZSV::from("語")]
+        {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_guess_delimiter() -> Result<(), ()> {
+        let csv: ZSV =
+            ZSV::from("name,age,note\nzane,30,\"likes tabs\tand; semis\"\nkai,25,none\n");
+        if csv.guess_delimiter() != Some(',') {
+            return Err(());
+        }
+        let tsv: ZSV =
+            ZSV::from("name\tage\tnote\nzane\t30\tcommas, everywhere, here\nkai\t25\tnone\n");
+        if tsv.guess_delimiter() != Some('\t') {
+            return Err(());
+        }
+        let piped: ZSV = ZSV::from("a|b\nc|d\n");
+        if piped.guess_delimiter() != Some('|')
+            || ZSV::from("just text\nmore").guess_delimiter().is_some()
+        {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_into_boxed_str() -> Result<(), ()> {
+        let mut data: String = String::with_capacity(64);
+        data.push_str("interned é");
+        let boxed: Box<str> = ZSV { data }.into_boxed_str();
+        if &*boxed != "interned é" {
+            return Err(());
+        }
+        let back: String = boxed.into_string();
+        if back.capacity() != back.len() {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_display_and_deref() -> Result<(), ()> {
+        fn takes_str(s: &str) -> usize {
+            s.len()
+        }
+        let sv: ZSV = ZSV::from("Grüße");
+        if format!("{}", sv) != "Grüße" || format!("[{sv:>7}]") != "[  Grüße]" {
+            return Err(());
+        }
+        if takes_str(&sv) != 7 || (*sv).to_uppercase() != "GRÜSSE" || sv.is_char_boundary(3) {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_zsv_ref() -> Result<(), ()> {
+        let owned: ZSV = ZSV::from("key=value=more");
+        let view: ZSVRef = owned.as_view();
+        if view.find_char('=') != owned.find_char('=')
+            || view.rfind_string(&ZSVRef::from("=")) != Some(9)
+        {
+            return Err(());
+        }
+        let (left, right) = view.split_char('=');
+        if left != Some(ZSVRef::from("key")) || right != Some(ZSVRef::from("=value=more")) {
+            return Err(());
+        }
+        if view
+            .split_string(&ZSVRef::from("value"))
+            .1
+            .map(|r| r.to_zsv())
+            != owned.split_string(&ZSV::from("value")).1
+        {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_zsv_ref_borrows_from_buffer() -> Result<(), ()> {
+        let mut buffer: String = String::new();
+        for i in 0..5000 {
+            buffer.push_str(&format!("field{},", i));
+        }
+        let range = buffer.as_bytes().as_ptr_range();
+        let view: ZSVRef = ZSVRef::from(buffer.as_str());
+        let pieces: Vec<ZSVRef> = view.split_all_char(',');
+        if pieces.len() != 5001 || pieces[1234] != ZSVRef::from("field1234") {
+            return Err(());
+        }
+        // Every piece must point into the original buffer rather than at a
+        // freshly allocated copy.
+        for piece in &pieces {
+            let piece_range = piece.data.as_bytes().as_ptr_range();
+            if piece_range.start < range.start || piece_range.end > range.end {
+                return Err(());
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_replace_nth() -> Result<(), ()> {
+        let data: ZSV = ZSV::from("a-b-c-d");
+        let from: ZSV = ZSV::from("-");
+        let to: ZSV = ZSV::from("+");
+        if data.replace_nth(&from, &to, 2) != ZSV::from("a-b+c-d") {
+            return Err(());
+        }
+        if data.replace_nth(&from, &to, 4) != data || data.replace_nth(&from, &to, 0) != data {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_starts_ends_with() -> Result<(), ()> {
+        let data: ZSV = ZSV::from("héllo");
+        if !data.starts_with_char('h') || data.starts_with_char('é') {
+            return Err(());
+        }
+        if !data.ends_with_char('o') || data.ends_with_char('l') {
+            return Err(());
+        }
+        if !data.starts_with(&ZSV::from("hé")) || data.starts_with(&ZSV::from("llo")) {
+            return Err(());
+        }
+        if !data.ends_with(&ZSV::from("llo")) || data.ends_with(&ZSV::from("hé")) {
+            return Err(());
+        }
+        let empty: ZSV = ZSV::from("");
+        if !data.starts_with(&empty) || !data.ends_with(&empty) || !empty.starts_with(&empty) {
+            return Err(());
+        }
+        let too_long: ZSV = ZSV::from("héllo world");
+        if data.starts_with(&too_long) || data.ends_with(&too_long) {
+            return Err(());
+        }
+        if empty.starts_with_char('h') || empty.ends_with_char('h') {
+            return Err(());
+        }
+        // The `str` methods are still reachable by dereferencing first.
+        if !(*data).starts_with("hé") || !(*data).ends_with('o') {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_minhash() -> Result<(), ()> {
+        let agreement = |a: &[u64], b: &[u64]| a.iter().zip(b).filter(|(x, y)| x == y).count();
+        let base: Vec<u64> = ZSV::from("the quick brown fox jumps over the lazy dog").minhash(128);
+        let similar: Vec<u64> =
+            ZSV::from("the quick brown fox jumped over the lazy dog").minhash(128);
+        let different: Vec<u64> = ZSV::from("lorem ipsum dolor sit amet, consectetur").minhash(128);
+        if base.len() != 128
+            || base != ZSV::from("the quick brown fox jumps over the lazy dog").minhash(128)
+        {
+            return Err(());
+        }
+        if agreement(&base, &similar) <= agreement(&base, &different) {
+            return Err(());
+        }
+        if ZSV::from("").minhash(4) != vec![u64::MAX; 4] {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_csv_fields_detailed() -> Result<(), ()> {
+        let row: ZSV = ZSV::from("plain,\"quoted, with comma\",\"say \"\"hi\"\"\",");
+        let fields: Vec<CsvField> = row.csv_fields_detailed(',');
+        let expected: Vec<CsvField> = vec![
+            CsvField {
+                value: ZSV::from("plain"),
+                was_quoted: false,
+            },
+            CsvField {
+                value: ZSV::from("quoted, with comma"),
+                was_quoted: true,
+            },
+            CsvField {
+                value: ZSV::from("say \"hi\""),
+                was_quoted: true,
+            },
+            CsvField {
+                value: ZSV::from(""),
+                was_quoted: false,
+            },
+        ];
+        if fields != expected {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_string_pathological() -> Result<(), ()> {
+        // With a brute-force matcher this input costs O(n * m) comparisons.
+        let mut haystack: String = "a".repeat(200_000);
+        haystack.push('b');
+        let data: ZSV = ZSV::from(haystack);
+        let needle: ZSV = ZSV::from(format!("{}b", "a".repeat(5_000)));
+        if data.find_string(&needle) != Some(195_000) || data.rfind_string(&needle) != Some(195_000)
+        {
+            return Err(());
+        }
+        let missing: ZSV = ZSV::from(format!("{}c", "a".repeat(5_000)));
+        if data.find_string(&missing).is_some() || data.rfind_string(&missing).is_some() {
+            return Err(());
+        }
+        let repeated: ZSV = ZSV::from("abcabcab");
+        if repeated.find_string(&ZSV::from("cab")) != Some(2)
+            || repeated.rfind_string(&ZSV::from("cab")) != Some(5)
+        {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_contains() -> Result<(), ()> {
+        let data: ZSV = ZSV::from("naïve café");
+        if !data.contains_char('ï') || !data.contains_char('é') || data.contains_char('x') {
+            return Err(());
+        }
+        if !data.contains(&ZSV::from("ve caf")) || !data.contains(&ZSV::from("é")) {
+            return Err(());
+        }
+        if data.contains(&ZSV::from("cafe")) || data.contains(&ZSV::from("naïve café!")) {
+            return Err(());
+        }
+        if !data.contains(&ZSV::from("")) || !ZSV::from("").contains(&ZSV::from("")) {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_prefix_within_bytes() -> Result<(), ()> {
+        // "日" is 3 bytes, so a budget of 5 lands in the middle of "本".
+        let data: ZSV = ZSV::from("日本語");
+        if data.prefix_within_bytes(5) != ZSV::from("日")
+            || data.prefix_within_bytes(6) != ZSV::from("日本")
+        {
+            return Err(());
+        }
+        if data.prefix_within_bytes(2) != ZSV::from("") || data.prefix_within_bytes(100) != data {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_matches_with_context() -> Result<(), ()> {
+        let data: ZSV = ZSV::from("cat sat on the mat with a cat");
+        let found: Vec<ZSV> = data.matches_with_context(&ZSV::from("cat"), 4);
+        if found != vec![ZSV::from("cat sat"), ZSV::from("h a cat")] {
+            return Err(());
+        }
+        let middle: Vec<ZSV> =
+            ZSV::from("über the café here").matches_with_context(&ZSV::from("café"), 2);
+        if middle != vec![ZSV::from("e café h")] {
+            return Err(());
+        }
+        if !data.matches_with_context(&ZSV::from(""), 4).is_empty() {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_trim() -> Result<(), ()> {
+        let data: ZSV = ZSV::from("\t\n  hello world \u{3000}\n");
+        if data.trim() != ZSV::from("hello world") {
+            return Err(());
+        }
+        if data.trim_start() != ZSV::from("hello world \u{3000}\n")
+            || data.trim_end() != ZSV::from("\t\n  hello world")
+        {
+            return Err(());
+        }
+        let blank: ZSV = ZSV::from(" \t\r\n ");
+        if blank.trim() != ZSV::from("")
+            || blank.trim_start() != ZSV::from("")
+            || blank.trim_end() != ZSV::from("")
+        {
+            return Err(());
+        }
+        if ZSV::from("--a-b--").trim_matches_char('-') != ZSV::from("a-b") {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_unescape_unicode() -> Result<(), ()> {
+        let data: ZSV = ZSV::from(r#"a\nb\tc\\d\"e\u{1F600}fég😀"#);
+        if data.unescape_unicode() != Ok(ZSV::from("a\nb\tc\\d\"e😀fég😀")) {
+            return Err(());
+        }
+        if ZSV::from(r"ok \u12").unescape_unicode() != Err(UnescapeError::MalformedUnicode(3)) {
+            return Err(());
+        }
+        if ZSV::from(r"\uD83D").unescape_unicode() != Err(UnescapeError::InvalidCodePoint(0)) {
+            return Err(());
+        }
+        if ZSV::from(r"\u{zz}").unescape_unicode() != Err(UnescapeError::MalformedUnicode(0)) {
+            return Err(());
+        }
+        if ZSV::from(r"\u{110000}").unescape_unicode() != Err(UnescapeError::InvalidCodePoint(0)) {
+            return Err(());
+        }
+        if ZSV::from(r"x\q").unescape_unicode() != Err(UnescapeError::UnknownEscape(1)) {
+            return Err(());
+        }
+        if ZSV::from(r"x\").unescape_unicode() != Err(UnescapeError::UnknownEscape(1)) {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_eq_hash_ord() -> Result<(), ()> {
+        use std::collections::{BTreeSet, HashSet};
+        let mut seen: HashSet<ZSV> = HashSet::new();
+        for word in ["pear", "apple", "fig"] {
+            if !seen.insert(ZSV::from(word)) {
+                return Err(());
+            }
+        }
+        if seen.insert(ZSV::from("apple")) || seen.len() != 3 {
+            return Err(());
+        }
+        let mut words: Vec<ZSV> = vec![
+            ZSV::from("pear"),
+            ZSV::from("Zebra"),
+            ZSV::from("apple"),
+            ZSV::from("app"),
+        ];
+        words.sort();
+        if words
+            != vec![
+                ZSV::from("Zebra"),
+                ZSV::from("app"),
+                ZSV::from("apple"),
+                ZSV::from("pear"),
+            ]
+        {
+            return Err(());
+        }
+        let ordered: BTreeSet<ZSV> = words.into_iter().collect();
+        if ordered.iter().next() != Some(&ZSV::from("Zebra")) {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_replace() -> Result<(), ()> {
+        let data: ZSV = ZSV::from("one fish two fish");
+        if data.replace(&ZSV::from("fish"), &ZSV::from("whale")) != ZSV::from("one whale two whale")
+        {
+            return Err(());
+        }
+        if data.replace(&ZSV::from("fish"), &ZSV::from("ox")) != ZSV::from("one ox two ox") {
+            return Err(());
+        }
+        if data.replace(&ZSV::from("cat"), &ZSV::from("dog")) != data
+            || data.replace(&ZSV::from(""), &ZSV::from("x")) != data
+        {
+            return Err(());
+        }
+        if ZSV::from("aaaa").replace(&ZSV::from("aa"), &ZSV::from("b")) != ZSV::from("bb") {
+            return Err(());
+        }
+        if ZSV::from("a-b-ç").replace_char('-', 'é') != ZSV::from("aébéç") {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_distinct_line_count() -> Result<(), ()> {
+        if ZSV::from("a\nb\na\r\nc\nb\n").distinct_line_count() != 3 {
+            return Err(());
+        }
+        if ZSV::from("x\ny\nz").distinct_line_count() != 3
+            || ZSV::from("").distinct_line_count() != 0
+        {
+            return Err(());
+        }
+        if ZSV::from("a\na \n").distinct_line_count() != 2 {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_case_fold_key() -> Result<(), ()> {
+        let key: ZSV = ZSV::from("i");
+        for variant in ["İ", "I", "ı"] {
+            if ZSV::from(variant).to_case_fold_key() != key {
+                return Err(());
+            }
+        }
+        if ZSV::from("Straße").to_case_fold_key() != ZSV::from("STRASSE").to_case_fold_key() {
+            return Err(());
+        }
+        if ZSV::from("ΟΔΟΣ").to_case_fold_key() != ZSV::from("οδος").to_case_fold_key() {
+            return Err(());
+        }
+        if ZSV::from("ﬁle").to_case_fold_key() != ZSV::from("FILE").to_case_fold_key() {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() -> Result<(), ()> {
+        let data: ZSV = ZSV::from("héllo \"world\"");
+        let json: String = serde_json::to_string(&data).map_err(|_| ())?;
+        if json != "\"héllo \\\"world\\\"\"" {
+            return Err(());
+        }
+        let back: ZSV = serde_json::from_str(&json).map_err(|_| ())?;
+        if back != data {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_safe_split_index() -> Result<(), ()> {
+        // "é" takes up bytes 1 and 2.
+        let data: ZSV = ZSV::from("héllo");
+        if !data.can_split_at_byte(1) || data.can_split_at_byte(2) || data.can_split_at_byte(6) {
+            return Err(());
+        }
+        if data.safe_split_index(3) != Some((ZSV::from("hé"), ZSV::from("llo"))) {
+            return Err(());
+        }
+        if data.safe_split_index(2).is_some() || data.safe_split_index(6).is_some() {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_from_cstring() -> Result<(), ()> {
+        let valid: CString = CString::new("héllo").map_err(|_| ())?;
+        if ZSV::try_from_cstring(&valid) != Ok(ZSV::from("héllo")) {
+            return Err(());
+        }
+        let invalid: CString = CString::new(vec![b'o', b'k', 0xFF, 0xFF]).map_err(|_| ())?;
+        if ZSV::try_from_cstring(&invalid).is_ok() {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_len_and_char_count() -> Result<(), ()> {
+        let data: ZSV = ZSV::from("café");
+        if data.len() != 5 || data.char_count() != 4 || data.is_empty() {
+            return Err(());
+        }
+        if data.find_char('é') != Some(3) || !ZSV::from("").is_empty() {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_replace_many() -> Result<(), ()> {
+        let data: ZSV = ZSV::from("a ab abc");
+        let short_first: Vec<(ZSV, ZSV)> = vec![
+            (ZSV::from("a"), ZSV::from("1")),
+            (ZSV::from("abc"), ZSV::from("3")),
+        ];
+        if data.replace_many(&short_first) != ZSV::from("1 1b 1bc") {
+            return Err(());
+        }
+        let long_first: Vec<(ZSV, ZSV)> = vec![
+            (ZSV::from("abc"), ZSV::from("3")),
+            (ZSV::from("a"), ZSV::from("1")),
+        ];
+        if data.replace_many(&long_first) != ZSV::from("1 1b 3") {
+            return Err(());
+        }
+        // Output is never rescanned, so swapping two names works.
+        let swap: Vec<(ZSV, ZSV)> = vec![
+            (ZSV::from("cat"), ZSV::from("dog")),
+            (ZSV::from("dog"), ZSV::from("cat")),
+            (ZSV::from(""), ZSV::from("!")),
+        ];
+        if ZSV::from("cat chases dog").replace_many(&swap) != ZSV::from("dog chases cat") {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_lines() -> Result<(), ()> {
+        let expected: Vec<ZSV> = vec![ZSV::from("one"), ZSV::from(""), ZSV::from("three")];
+        if ZSV::from("one\n\nthree\n").lines().collect::<Vec<ZSV>>() != expected {
+            return Err(());
+        }
+        if ZSV::from("one\r\n\r\nthree\r\n")
+            .lines()
+            .collect::<Vec<ZSV>>()
+            != expected
+        {
+            return Err(());
+        }
+        if ZSV::from("one\n\nthree").lines().collect::<Vec<ZSV>>() != expected {
+            return Err(());
+        }
+        if ZSV::from("").lines().count() != 0 {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_sorted_char_signature() -> Result<(), ()> {
+        let listen: ZSV = ZSV::from("listen").sorted_char_signature();
+        if listen != ZSV::from("silent").sorted_char_signature() || listen != ZSV::from("eilnst") {
+            return Err(());
+        }
+        if listen == ZSV::from("Silent").sorted_char_signature() {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_pattern() -> Result<(), ()> {
+        let data: ZSV = ZSV::from("héllo wörld");
+        if data.find('ö') != data.find_char('ö') || data.find('ö') != Some(8) {
+            return Err(());
+        }
+        let needle: ZSV = ZSV::from("wö");
+        if data.find("wö") != data.find_string(&needle) || data.find(&needle) != Some(7) {
+            return Err(());
+        }
+        if data.find('z').is_some()
+            || data.find("") != Some(0)
+            || data.find(&ZSV::from("xyz")).is_some()
+        {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_paragraphs() -> Result<(), ()> {
+        let data: ZSV =
+            ZSV::from("\n\nfirst line\nstill first\n\nsecond\n \t\n\n\nthird\r\nmore\r\n\n");
+        let expected: Vec<ZSV> = vec![
+            ZSV::from("first line\nstill first"),
+            ZSV::from("second"),
+            ZSV::from("third\r\nmore"),
+        ];
+        if data.split_paragraphs() != expected {
+            return Err(());
+        }
+        if !ZSV::from(" \n\n").split_paragraphs().is_empty()
+            || ZSV::from("one").split_paragraphs() != vec![ZSV::from("one")]
+        {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_visit_matches() -> Result<(), ()> {
+        let data: ZSV = ZSV::from("ab·ab·aab·abab");
+        let needle: ZSV = ZSV::from("ab");
+        let mut seen: Vec<(usize, ZSV)> = Vec::new();
+        data.visit_matches(&needle, |indx, m| seen.push((indx, m.clone())));
+        if seen.iter().map(|(i, _)| *i).collect::<Vec<usize>>() != data.find_all(&needle) {
+            return Err(());
+        }
+        if data.find_all(&needle) != vec![0, 4, 9, 13, 15] || seen.iter().any(|(_, m)| *m != needle)
+        {
+            return Err(());
+        }
+        if ZSV::from("aaaa").find_all(&ZSV::from("aa")) != vec![0, 2]
+            || !data.find_all(&ZSV::from("")).is_empty()
+        {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_once_char() -> Result<(), ()> {
+        if ZSV::from("key=value=x").split_once_char('=')
+            != Some((ZSV::from("key"), ZSV::from("value=x")))
+        {
+            return Err(());
+        }
+        if ZSV::from("no separator").split_once_char('=').is_some() {
+            return Err(());
+        }
+        if ZSV::from("=value").split_once_char('=') != Some((ZSV::from(""), ZSV::from("value"))) {
+            return Err(());
+        }
+        if ZSV::from("clé→").split_once_char('→') != Some((ZSV::from("clé"), ZSV::from(""))) {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_from() -> Result<(), ()> {
+        let data: ZSV = ZSV::from("a·b·c·");
+        let mut found: Vec<usize> = Vec::new();
+        let mut start: usize = 0;
+        while let Some(indx) = data.find_char_from('·', start) {
+            found.push(indx);
+            start = indx + 1;
+        }
+        if found != vec![1, 4, 7] || data.find_char_from('a', 100).is_some() {
+            return Err(());
+        }
+        let text: ZSV = ZSV::from("abcabcabc");
+        let needle: ZSV = ZSV::from("bc");
+        if text.find_string_from(&needle, 2) != Some(4)
+            || text.find_string_from(&needle, 8).is_some()
+        {
+            return Err(());
+        }
+        if text.find_string_from(&ZSV::from(""), 9) != Some(9)
+            || text.find_string_from(&needle, 10).is_some()
+        {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_prefix_function() -> Result<(), ()> {
+        if ZSV::from("ababaca").prefix_function() != vec![0, 0, 1, 2, 3, 0, 1] {
+            return Err(());
+        }
+        if ZSV::from("ééaéé").prefix_function() != vec![0, 1, 0, 1, 2]
+            || !ZSV::from("").prefix_function().is_empty()
+        {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_count() -> Result<(), ()> {
+        let data: ZSV = ZSV::from("né, né, nénette");
+        if data.count_char('é') != 3 || data.count_char('z') != 0 {
+            return Err(());
+        }
+        if data.count(&ZSV::from("né")) != 3 || data.count(&ZSV::from("xyz")) != 0 {
+            return Err(());
+        }
+        if ZSV::from("aaaa").count(&ZSV::from("aa")) != 2 || data.count(&ZSV::from("")) != 0 {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_address_list() -> Result<(), ()> {
+        let data: ZSV = ZSV::from("\"A, B\" <a@x>, c@y");
+        if data.split_address_list() != vec![ZSV::from("\"A, B\" <a@x>"), ZSV::from("c@y")] {
+            return Err(());
+        }
+        let tricky: ZSV = ZSV::from("\"Say \\\"hi, there\\\"\" <s@x>, <odd,local@y>, ,");
+        let expected: Vec<ZSV> = vec![
+            ZSV::from("\"Say \\\"hi, there\\\"\" <s@x>"),
+            ZSV::from("<odd,local@y>"),
+        ];
+        if tricky.split_address_list() != expected {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_unified_diff() -> Result<(), ()> {
+        let before: ZSV = ZSV::from("a\nb\nc\nd\ne\nf\ng\nh\n");
+        let inserted: ZSV = ZSV::from("a\nb\nc\nd\nnew\ne\nf\ng\nh\n");
+        if before.unified_diff(&inserted, 1) != ZSV::from("@@ -4,2 +4,3 @@\n d\n+new\n e\n") {
+            return Err(());
+        }
+        let deleted: ZSV = ZSV::from("b\nc\nd\ne\nf\ng\n");
+        let expected: ZSV = ZSV::from("@@ -1,2 +1 @@\n-a\n b\n@@ -7,2 +6 @@\n g\n-h\n");
+        if before.unified_diff(&deleted, 1) != expected {
+            return Err(());
+        }
+        // With more context the two hunks merge into one.
+        if !before
+            .unified_diff(&deleted, 3)
+            .starts_with(&ZSV::from("@@ -1,8 +1,6 @@\n-a\n"))
+        {
+            return Err(());
+        }
+        if ZSV::from("").unified_diff(&ZSV::from("x\n"), 3) != ZSV::from("@@ -0,0 +1 @@\n+x\n") {
+            return Err(());
+        }
+        if !before.unified_diff(&before, 3).is_empty() {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_substring() -> Result<(), ()> {
+        if ZSV::from("hello world").substring(6, 11) != Some(ZSV::from("world")) {
+            return Err(());
+        }
+        let data: ZSV = ZSV::from("日本語テキスト");
+        if data.substring(1, 4) != Some(ZSV::from("本語テ"))
+            || data.substring(0, 7) != Some(data.clone())
+        {
+            return Err(());
+        }
+        if data.substring(3, 3) != Some(ZSV::from(""))
+            || data.substring(7, 7) != Some(ZSV::from(""))
+        {
+            return Err(());
+        }
+        if data.substring(2, 8).is_some()
+            || data.substring(8, 8).is_some()
+            || data.substring(4, 2).is_some()
+        {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_matches_glob() -> Result<(), ()> {
+        let file: ZSV = ZSV::from("file.txt");
+        for pattern in ["*.txt", "fil?.txt", "*", "file.txt*", "f*e*t", "*.*"] {
+            if !file.matches_glob(&ZSV::from(pattern)) {
+                return Err(());
+            }
+        }
+        for pattern in ["*.rs", "fil?", "?file.txt", "file.tx", ""] {
+            if file.matches_glob(&ZSV::from(pattern)) {
+                return Err(());
+            }
+        }
+        if !ZSV::from("").matches_glob(&ZSV::from("*"))
+            || !ZSV::from("résumé").matches_glob(&ZSV::from("r?sum?"))
+        {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_and_concat() -> Result<(), ()> {
+        let a: ZSV = ZSV::from("foo");
+        let b: ZSV = ZSV::from("bär");
+        if a.clone() + b.clone() != ZSV::from(format!("{}{}", a.data, b.data)) {
+            return Err(());
+        }
+        let parts: Vec<ZSV> = vec![
+            ZSV::from("a"),
+            ZSV::from(""),
+            ZSV::from("bc"),
+            ZSV::from("d"),
+        ];
+        if ZSV::concat(&parts) != ZSV::from("abcd") || ZSV::concat(&[]) != ZSV::from("") {
+            return Err(());
+        }
+        let mut total: ZSV = ZSV::from("");
+        for i in 0..4 {
+            total += ZSV::from(i.to_string());
+        }
+        if total != ZSV::from("0123") {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_join() -> Result<(), ()> {
+        let data: ZSV = ZSV::from("alpha,béta,gamma");
+        if ZSV::join(&data.split_all_char(','), &ZSV::from(",")) != data {
+            return Err(());
+        }
+        if ZSV::join_char(&data.split_all_char(','), ';') != ZSV::from("alpha;béta;gamma") {
+            return Err(());
+        }
+        if ZSV::join(&[], &ZSV::from(", ")) != ZSV::from("")
+            || ZSV::join(&[ZSV::from("solo")], &ZSV::from(", ")) != ZSV::from("solo")
+        {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_expand_braces() -> Result<(), ()> {
+        let to_zsvs = |items: &[&str]| items.iter().map(|s| ZSV::from(*s)).collect::<Vec<ZSV>>();
+        if ZSV::from("a{b,c}d").expand_braces() != to_zsvs(&["abd", "acd"]) {
+            return Err(());
+        }
+        if ZSV::from("{x,y}-{1,2}").expand_braces() != to_zsvs(&["x-1", "x-2", "y-1", "y-2"]) {
+            return Err(());
+        }
+        if ZSV::from("a{b,c{d,e},}f").expand_braces() != to_zsvs(&["abf", "acdf", "acef", "af"]) {
+            return Err(());
+        }
+        if ZSV::from("{a}{b").expand_braces() != to_zsvs(&["{a}{b"]) {
+            return Err(());
+        }
+        if ZSV::from("{x{a,b}").expand_braces() != to_zsvs(&["{xa", "{xb"]) {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_case_conversion_and_find_ignore_ascii_case() -> Result<(), ()> {
+        let data: ZSV = ZSV::from("Grüße, Hello WORLD");
+        if data.to_lowercase() != ZSV::from("grüße, hello world")
+            || data.to_uppercase() != ZSV::from("GRÜSSE, HELLO WORLD")
+        {
+            return Err(());
+        }
+        if data.find_ignore_ascii_case(&ZSV::from("hello")) != Some(9)
+            || data.find_ignore_ascii_case(&ZSV::from("world")) != Some(15)
+        {
+            return Err(());
+        }
+        if data.find_ignore_ascii_case(&ZSV::from("GRÜ")).is_some()
+            || data.find_ignore_ascii_case(&ZSV::from("grü")) != Some(0)
+        {
+            return Err(());
+        }
+        if data.find_ignore_ascii_case(&ZSV::from("")) != Some(0)
+            || ZSV::from("hi")
+                .find_ignore_ascii_case(&ZSV::from("high"))
+                .is_some()
+        {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_code_points() -> Result<(), ()> {
+        if ZSV::from("aé€😀").code_points() != vec![0x61, 0xe9, 0x20ac, 0x1f600]
+            || !ZSV::from("").code_points().is_empty()
+        {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_as_ref_and_borrow_str() -> Result<(), ()> {
+        fn takes<S: AsRef<str>>(s: S) -> usize {
+            s.as_ref().len()
+        }
+        let data: ZSV = ZSV::from("café");
+        if takes(&data) != 5 || takes(data.clone()) != 5 {
+            return Err(());
+        }
+        // Both the explicit trait call and method syntax reach the trait,
+        // since the inherent bridge to `ZSVRef` is named `as_view`.
+        let method: &str = data.as_ref();
+        if AsRef::<str>::as_ref(&data) != "café" || method != "café" {
+            return Err(());
+        }
+        let mut counts: std::collections::HashMap<ZSV, usize> = std::collections::HashMap::new();
+        counts.insert(ZSV::from("apple"), 3);
+        counts.insert(ZSV::from("pear"), 1);
+        if counts.get("apple") != Some(&3) || counts.contains_key("plum") {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize_quotes() -> Result<(), ()> {
+        let data: ZSV = ZSV::from("“It’s fine,” she said — pages 3–5, ‘café’ „ok“");
+        if data.normalize_quotes()
+            != ZSV::from("\"It's fine,\" she said -- pages 3-5, 'café' \"ok\"")
+        {
+            return Err(());
+        }
+        let plain: ZSV = ZSV::from("already \"plain\" - text");
+        if plain.normalize_quotes() != plain {
+            return Err(());
+        }
+        Ok(())
     }
 }